@@ -0,0 +1,89 @@
+//! Generates the table of the solution registry (see `src/template/registry.rs`).
+//!
+//! With the `registry` feature, every solution binary in `src/bin` is compiled into the library as
+//! a module, so that a single executable can run any day in-process.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let registry = Path::new(&out_dir).join("registry.rs");
+
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        fs::write(registry, "").unwrap();
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+
+    let mut names: Vec<String> = fs::read_dir(Path::new(&manifest_dir).join("src").join("bin"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let name = path.file_stem()?.to_str()?.to_string();
+                    is_solution_name(&name).then_some(name)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_unstable();
+
+    let lib =
+        fs::read_to_string(Path::new(&manifest_dir).join("src").join("lib.rs")).unwrap_or_default();
+
+    let mut modules = String::new();
+    let mut table = String::from("pub static SOLUTIONS: &[&Solution] = &[\n");
+
+    for name in &names {
+        let path = Path::new(&manifest_dir)
+            .join("src")
+            .join("bin")
+            .join(format!("{name}.rs"));
+        for attribute in missing_crate_attributes(&path, &lib) {
+            println!(
+                "cargo:warning=src/bin/{name}.rs uses `#![{attribute}]`, repeat it in src/lib.rs for the registry"
+            );
+        }
+
+        modules.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code, unused_attributes, unused_imports)]\nmod solution_{name};\n",
+            path.display().to_string()
+        ));
+        table.push_str(&format!("    &solution_{name}::SOLUTION,\n"));
+    }
+    table.push_str("];\n");
+
+    fs::write(registry, format!("{modules}\n{table}")).unwrap();
+}
+
+/// Returns the inner attributes of a source file, like `feature(...)`, that only apply at the root
+/// of a crate and are not repeated in the library.
+fn missing_crate_attributes(path: &Path, lib: &str) -> Vec<String> {
+    let source = fs::read_to_string(path).unwrap_or_default();
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#![")?.strip_suffix(']'))
+        .filter(|attribute| !lib.contains(attribute))
+        .map(String::from)
+        .collect()
+}
+
+/// Whether a binary name has the `{year}_{day}` shape of a solution.
+fn is_solution_name(name: &str) -> bool {
+    match name.split_once('_') {
+        Some((year, day)) => {
+            year.len() == 4
+                && day.len() == 2
+                && year.chars().chain(day.chars()).all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
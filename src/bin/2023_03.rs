@@ -76,7 +76,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-/// --- Part Two ---
+// --- Part Two ---
 
 fn get_number(line: &str, from: usize) -> Option<u32> {
     let numbers_to_end = line[from..]
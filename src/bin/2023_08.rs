@@ -139,43 +139,25 @@ mod tests {
 
     #[test]
     fn test_part_one_example_one() {
-        let example = r"RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-        let result = part_one(example);
+        let result = part_one(&advent_of_code::template::read_file_numbered(
+            "examples", YEAR, DAY, 1,
+        ));
         assert_eq!(2, result.unwrap());
     }
 
     #[test]
     fn test_part_one_example_two() {
-        let example = r"LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-        let result = part_one(example);
+        let result = part_one(&advent_of_code::template::read_file_numbered(
+            "examples", YEAR, DAY, 2,
+        ));
         assert_eq!(6, result.unwrap());
     }
 
     #[test]
     fn test_part_two_example() {
-        let example = r"LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-        let result = part_two(example);
+        let result = part_two(&advent_of_code::template::read_file_numbered(
+            "examples", YEAR, DAY, 3,
+        ));
         assert_eq!(6, result.unwrap());
     }
 }
@@ -1,13 +1,21 @@
 use rayon::prelude::*;
 
-advent_of_code::solution!(2023, 9);
-
-fn parse_input(input: &str) -> impl ParallelIterator<Item = Vec<i64>> + '_ {
-    input.par_lines().filter(|l| !l.is_empty()).map(|l| {
-        l.split_ascii_whitespace()
-            .map(|s| s.parse::<i64>().expect("To be able to parse number `{l}`"))
-            .collect::<Vec<_>>()
-    })
+advent_of_code::solution!(2023, 9, parse_input);
+
+pub struct Histories(Vec<Vec<i64>>);
+
+fn parse_input(input: &str) -> Histories {
+    let histories = input
+        .par_lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            l.split_ascii_whitespace()
+                .map(|s| s.parse::<i64>().expect("To be able to parse number `{l}`"))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Histories(histories)
 }
 
 // -- utility --
@@ -31,9 +39,8 @@ fn next_number(vec: &[i64]) -> i64 {
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let input = parse_input(input);
-    let sum = input.map(|v| next_number(&v)).sum();
+pub fn part_one(histories: &Histories) -> Option<i64> {
+    let sum = histories.0.par_iter().map(|v| next_number(v)).sum();
 
     Some(sum)
 }
@@ -50,9 +57,8 @@ fn previous_number(vec: &[i64]) -> i64 {
     }
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let input = parse_input(input);
-    let sum = input.map(|v| previous_number(&v)).sum();
+pub fn part_two(histories: &Histories) -> Option<i64> {
+    let sum = histories.0.par_iter().map(|v| previous_number(v)).sum();
 
     Some(sum)
 }
@@ -100,13 +106,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        let input = advent_of_code::template::read_file("examples", YEAR, DAY);
+        let result = part_one(&parse_input(&input));
         assert_eq!(114, result.unwrap());
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
+        let input = advent_of_code::template::read_file("examples", YEAR, DAY);
+        let result = part_two(&parse_input(&input));
         assert_eq!(2, result.unwrap());
     }
 }
@@ -0,0 +1,107 @@
+//! Date arithmetic for the Advent of Code calendar.
+//!
+//! Puzzles unlock at midnight in UTC-5, so every date here is a date in that timezone. Conversions
+//! between days and dates follow Howard Hinnant's `days_from_civil` / `civil_from_days`.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Offset of the timezone puzzles unlock in (UTC-5), in seconds.
+pub(crate) const UNLOCK_OFFSET_SECS: i64 = 5 * 60 * 60;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Returns the current time in seconds since the unix epoch.
+pub(crate) fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|d| i64::try_from(d.as_secs()).ok())
+        .unwrap_or_default()
+}
+
+/// Returns the date in UTC-5 of a unix timestamp as `(year, month, day)`.
+pub(crate) fn event_date(unix_secs: i64) -> (i64, u32, u32) {
+    civil_from_days((unix_secs - UNLOCK_OFFSET_SECS).div_euclid(SECS_PER_DAY))
+}
+
+/// Returns the unix timestamp of midnight in UTC-5 at the start of a date.
+pub(crate) fn event_midnight(year: i64, month: u32, day: u32) -> i64 {
+    days_from_civil(year, month, day) * SECS_PER_DAY + UNLOCK_OFFSET_SECS
+}
+
+/// Returns the year of the latest event that has started at `unix_secs`.
+pub(crate) fn latest_event_year(unix_secs: i64) -> i64 {
+    let (year, _, _) = event_date(unix_secs);
+    if unix_secs >= event_midnight(year, 12, 1) {
+        year
+    } else {
+        year - 1
+    }
+}
+
+/// Returns the number of days since the unix epoch of a date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let month_from_march = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the date `days` after the unix epoch as `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    // both values are within their ranges by construction.
+    (
+        year,
+        u32::try_from(month).unwrap_or_default(),
+        u32::try_from(day).unwrap_or_default(),
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{civil_from_days, days_from_civil, event_date, event_midnight, latest_event_year};
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+        assert_eq!(civil_from_days(19_692), (2023, 12, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn uses_unlock_timezone() {
+        // 2023-12-01T04:59:59Z is still November 30th in UTC-5.
+        let midnight = event_midnight(2023, 12, 1);
+        assert_eq!(midnight, 1_701_406_800);
+        assert_eq!(event_date(midnight - 1), (2023, 11, 30));
+        assert_eq!(event_date(midnight), (2023, 12, 1));
+    }
+
+    #[test]
+    fn finds_latest_event_year() {
+        let midnight = event_midnight(2024, 12, 1);
+        assert_eq!(latest_event_year(midnight - 1), 2023);
+        assert_eq!(latest_event_year(midnight), 2024);
+        assert_eq!(latest_event_year(event_midnight(2025, 6, 1)), 2024);
+    }
+}
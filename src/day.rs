@@ -2,6 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -12,7 +14,8 @@ use std::str::FromStr;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
 pub struct Day(u8);
 
 impl Day {
@@ -55,6 +58,20 @@ impl PartialOrd<u8> for Day {
     }
 }
 
+impl TryFrom<u8> for Day {
+    type Error = DayFromStrError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Self::new(day).ok_or(DayFromStrError)
+    }
+}
+
+impl From<Day> for u8 {
+    fn from(day: Day) -> Self {
+        day.0
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl FromStr for Day {
@@ -86,14 +103,24 @@ pub fn all_days() -> AllDays {
 }
 
 /// An iterator that yields every day of advent from the 1st to the 25th.
+/// Use [`Year::days`](crate::Year::days) for the days of a specific event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(25)
+    }
+
+    /// Creates an iterator over the days from the 1st up to `last`, which is at most the 25th.
+    pub(crate) fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(25),
+        }
     }
 }
 
@@ -101,7 +128,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -1,6 +1,18 @@
+// Crate-level attributes of solution binaries have to be repeated here for the registry.
+#![cfg_attr(feature = "registry", feature(iter_map_windows))]
+
+// Lets solution binaries, which refer to this crate by name, compile as part of it.
+#[cfg(feature = "registry")]
+extern crate self as advent_of_code;
+
+mod calendar;
 mod day;
+mod part;
+mod puzzle;
 pub mod template;
 mod year;
 
 pub use day::*;
+pub use part::*;
+pub use puzzle::*;
 pub use year::*;
@@ -1,35 +1,113 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::benchmark_history::LABEL_ENV;
+use advent_of_code::template::commands::{
+    all, compare, download, examples, export, list, progress, read, scaffold, solve, status,
+    verify, wait,
+};
+use advent_of_code::template::get_year;
+use advent_of_code::Year;
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{path::PathBuf, process};
 
-    use advent_of_code::{Day, Year};
+    use advent_of_code::template::clock::{Clock, SystemClock};
+    use advent_of_code::template::{config, export::ExportFormat, get_year, options::RunOptions};
+    use advent_of_code::{Part, PuzzleId, Year};
 
     pub enum AppArguments {
         Download {
-            year: Year,
-            day: Day,
+            puzzle: PuzzleId,
+            examples: bool,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Read {
-            year: Year,
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            year: Year,
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Wait {
+            puzzle: PuzzleId,
         },
         Solve {
-            year: Year,
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
-            time: bool,
-            submit: Option<u8>,
+            options: RunOptions,
+            label: Option<String>,
         },
         All {
+            year: Option<Year>,
+            all_years: bool,
             release: bool,
             time: bool,
+            part: Option<Part>,
+            timeout: Option<u64>,
+            label: Option<String>,
+        },
+        Verify {
+            year: Option<Year>,
+            all_years: bool,
+        },
+        Export {
+            year: Option<Year>,
+            all_years: bool,
+            format: ExportFormat,
+            output: Option<PathBuf>,
+        },
+        Status {
+            year: Option<Year>,
+            all_years: bool,
         },
+        Progress {
+            year: Option<Year>,
+            all_years: bool,
+            sync: bool,
+            leaderboard: Option<PathBuf>,
+        },
+        List {
+            year: Option<Year>,
+            all_years: bool,
+        },
+        Compare {
+            year: Option<Year>,
+            all_years: bool,
+            baseline: Option<String>,
+            threshold: f64,
+        },
+    }
+
+    /// Parses a puzzle, e.g. `2023/5`, `5` or `today`, or `<year> <day>` as two arguments.
+    /// A second argument is only taken if the first one is a year, so that free arguments
+    /// after a lone day (e.g. the number of `--example [n]`) are left alone.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        Ok(puzzle_arg(args)?.parse()?)
+    }
+
+    /// Parses a puzzle like [`parse_puzzle`], also accepting the puzzles of the next event.
+    fn parse_upcoming_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let puzzle = puzzle_arg(args)?;
+        Ok(PuzzleId::parse_upcoming(
+            &puzzle,
+            get_year(),
+            SystemClock.now_secs(),
+        )?)
+    }
+
+    fn puzzle_arg(args: &mut pico_args::Arguments) -> Result<String, pico_args::Error> {
+        let first: String = args.free_from_str()?;
+
+        if first.len() == 4 && first.bytes().all(|b| b.is_ascii_digit()) {
+            let day: String = args.free_from_str()?;
+            Ok(format!("{first}/{day}"))
+        } else {
+            Ok(first)
+        }
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -39,26 +117,79 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                all_years: args.contains("--all-years"),
+                part: args.opt_value_from_str("--part")?,
+                timeout: args.opt_value_from_str("--timeout")?,
+                label: args.opt_value_from_str("--label")?,
+                year: args.opt_free_from_str()?,
             },
-            Some("download") => AppArguments::Download {
-                year: args.free_from_str()?,
-                day: args.free_from_str()?,
+            Some("verify") => AppArguments::Verify {
+                all_years: args.contains("--all-years"),
+                year: args.opt_free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
-                year: args.free_from_str()?,
-                day: args.free_from_str()?,
+            Some("list") => AppArguments::List {
+                all_years: args.contains("--all-years"),
+                year: args.opt_free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                year: args.free_from_str()?,
-                day: args.free_from_str()?,
+            Some("export") => AppArguments::Export {
+                all_years: args.contains("--all-years"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                output: args.opt_value_from_str("--output")?,
+                year: args.opt_free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
-                year: args.free_from_str()?,
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            Some("status") => AppArguments::Status {
+                all_years: args.contains("--all-years"),
+                year: args.opt_free_from_str()?,
+            },
+            Some("progress") => AppArguments::Progress {
+                all_years: args.contains("--all-years"),
+                sync: args.contains("--sync"),
+                leaderboard: args.opt_value_from_str("--leaderboard")?,
+                year: args.opt_free_from_str()?,
+            },
+            Some("compare") => AppArguments::Compare {
+                all_years: args.contains("--all-years"),
+                baseline: args.opt_value_from_str("--baseline")?,
+                threshold: args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(config::get().benchmark.regression_threshold),
+                year: args.opt_free_from_str()?,
             },
+            Some("download") => {
+                let examples = args.contains("--examples");
+                let puzzle = parse_puzzle(&mut args)?;
+                AppArguments::Download { puzzle, examples }
+            }
+            Some("examples") => {
+                let puzzle = parse_puzzle(&mut args)?;
+                AppArguments::Examples { puzzle }
+            }
+            Some("read") => {
+                let puzzle = parse_puzzle(&mut args)?;
+                AppArguments::Read { puzzle }
+            }
+            Some("scaffold") => {
+                let puzzle = parse_puzzle(&mut args)?;
+                AppArguments::Scaffold { puzzle }
+            }
+            Some("wait") => {
+                let puzzle = parse_upcoming_puzzle(&mut args)?;
+                AppArguments::Wait { puzzle }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let label = args.opt_value_from_str("--label")?;
+                let mut options = RunOptions::from_flags(&mut args)?;
+                let puzzle = parse_puzzle(&mut args)?;
+                options.take_example_number(&mut args)?;
+                options.validate()?;
+                AppArguments::Solve {
+                    puzzle,
+                    release,
+                    options,
+                    label,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -78,6 +209,23 @@ mod args {
     }
 }
 
+/// Labels the benchmarks recorded by child invocations so they can be used as a baseline.
+fn set_bench_label(label: Option<String>) {
+    if let Some(label) = label {
+        std::env::set_var(LABEL_ENV, label);
+    }
+}
+
+/// Returns the year a command runs for: [`None`] for every year with `--all-years`, or else the
+/// given year or the default year.
+fn resolve_year(year: Option<Year>, all_years: bool) -> Option<Year> {
+    if all_years {
+        None
+    } else {
+        year.or_else(get_year)
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -85,17 +233,77 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { year, day } => download::handle(year, day),
-            AppArguments::Read { year, day } => read::handle(year, day),
-            AppArguments::Scaffold { year, day } => scaffold::handle(year, day),
-            AppArguments::Solve {
+            AppArguments::All {
                 year,
-                day,
+                all_years,
                 release,
                 time,
-                submit,
-            } => solve::handle(year, day, release, time, submit),
+                part,
+                timeout,
+                label,
+            } => {
+                set_bench_label(label);
+                let year = resolve_year(year, all_years);
+                all::handle(year, release, time, part, timeout);
+            }
+            AppArguments::Verify { year, all_years } => {
+                verify::handle(resolve_year(year, all_years));
+            }
+            AppArguments::Export {
+                year,
+                all_years,
+                format,
+                output,
+            } => {
+                let year = resolve_year(year, all_years);
+                export::handle(year, format, output.as_deref());
+            }
+            AppArguments::Status { year, all_years } => {
+                status::handle(resolve_year(year, all_years));
+            }
+            AppArguments::Progress {
+                year,
+                all_years,
+                sync,
+                leaderboard,
+            } => {
+                let year = resolve_year(year, all_years);
+                progress::handle(year, sync, leaderboard.as_deref());
+            }
+            AppArguments::List { year, all_years } => {
+                list::handle(resolve_year(year, all_years));
+            }
+            AppArguments::Compare {
+                year,
+                all_years,
+                baseline,
+                threshold,
+            } => {
+                let year = resolve_year(year, all_years);
+                compare::handle(year, baseline.as_deref(), threshold);
+            }
+            AppArguments::Download {
+                puzzle,
+                examples: with_examples,
+            } => {
+                download::handle(puzzle);
+                if with_examples {
+                    examples::handle(puzzle.year(), puzzle.day());
+                }
+            }
+            AppArguments::Examples { puzzle } => examples::handle(puzzle.year(), puzzle.day()),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold { puzzle } => scaffold::handle(puzzle.year(), puzzle.day()),
+            AppArguments::Wait { puzzle } => wait::handle(puzzle),
+            AppArguments::Solve {
+                puzzle,
+                release,
+                options,
+                label,
+            } => {
+                set_bench_label(label);
+                solve::handle(puzzle.year(), puzzle.day(), release, &options);
+            }
         },
     };
 }
@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// One of the two parts of a puzzle.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::Part;
+/// let part: Part = "2".parse().unwrap();
+/// assert_eq!(part, Part::Two);
+/// assert_eq!(format!("Part {part}"), "Part 2")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Creates a [`Part`] from its number, returns [`None`] for anything but `1` and `2`.
+    pub fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub fn into_inner(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = PartFromStrError;
+
+    fn try_from(part: u8) -> Result<Self, Self::Error> {
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        part.into_inner()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = s.parse().map_err(|_| PartFromStrError)?;
+        Self::new(part).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting part 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,280 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{calendar, template, Day, DayFromStrError, Year, YearFromStrError};
+
+/// A puzzle of an event, i.e. a [`Year`] and a [`Day`] that is part of that year's event.
+///
+/// # Parsing
+/// A puzzle parses from `2023/5`, `2023-05` or `2023_05`, from a day number alone, which uses the
+/// [default year](crate::template::get_year), or from `today`, the puzzle of the current day of
+/// an event.
+///
+/// # Display
+/// This value displays as `{year}_{day}`, the name of its solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, year, PuzzleId};
+/// let id: PuzzleId = "2023/8".parse().unwrap();
+/// assert_eq!(id, PuzzleId::new(year!(2023), day!(8)).unwrap());
+/// assert_eq!(id.to_string(), "2023_08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "(Year, Day)", into = "(Year, Day)")]
+pub struct PuzzleId {
+    year: Year,
+    day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] if `day` is part of the event of `year`,
+    /// returns [`None`] otherwise.
+    pub fn new(year: Year, day: Day) -> Option<Self> {
+        if day > year.day_count() {
+            return None;
+        }
+        Some(Self { year, day })
+    }
+
+    /// Parses a [`PuzzleId`], using `default_year` for a day number alone and `unix_secs` as the
+    /// current time for `today`.
+    pub fn parse(
+        s: &str,
+        default_year: Option<Year>,
+        unix_secs: i64,
+    ) -> Result<Self, PuzzleIdError> {
+        Self::parse_until(s, default_year, unix_secs, Year::latest_at(unix_secs))
+    }
+
+    /// Parses a [`PuzzleId`] like [`PuzzleId::parse`], but also accepts the puzzles of the next
+    /// event, which are not unlocked yet.
+    pub fn parse_upcoming(
+        s: &str,
+        default_year: Option<Year>,
+        unix_secs: i64,
+    ) -> Result<Self, PuzzleIdError> {
+        Self::parse_until(s, default_year, unix_secs, Year::next_at(unix_secs))
+    }
+
+    fn parse_until(
+        s: &str,
+        default_year: Option<Year>,
+        unix_secs: i64,
+        last_year: Year,
+    ) -> Result<Self, PuzzleIdError> {
+        let s = s.trim();
+
+        if s.eq_ignore_ascii_case("today") {
+            return Self::today_at(unix_secs).ok_or(PuzzleIdError::NoPuzzleToday);
+        }
+
+        let (year, day) = match s.split_once(['/', '-', '_']) {
+            Some((year, day)) => {
+                let year = year
+                    .parse::<u16>()
+                    .ok()
+                    .and_then(|year| Year::try_from(year).ok())
+                    .filter(|year| *year <= last_year)
+                    .ok_or(PuzzleIdError::Year(YearFromStrError))?;
+                (year, day)
+            }
+            None => (default_year.ok_or(PuzzleIdError::NoDefaultYear)?, s),
+        };
+        let day = day.parse().map_err(PuzzleIdError::Day)?;
+
+        Self::new(year, day).ok_or(PuzzleIdError::NotInEvent { year, day })
+    }
+
+    /// Returns the puzzle that unlocked on the current day, if an event is running.
+    #[must_use]
+    pub fn today() -> Option<Self> {
+        Self::today_at(calendar::now_secs())
+    }
+
+    /// Returns the puzzle that unlocked on the day of `unix_secs`, if an event is running.
+    #[must_use]
+    pub fn today_at(unix_secs: i64) -> Option<Self> {
+        let (year, month, day) = calendar::event_date(unix_secs);
+        if month != 12 {
+            return None;
+        }
+        let year = Year::new_at(u16::try_from(year).ok()?, unix_secs)?;
+        Self::new(year, Day::new(u8::try_from(day).ok()?)?)
+    }
+
+    /// Returns the year of the puzzle.
+    #[must_use]
+    pub fn year(self) -> Year {
+        self.year
+    }
+
+    /// Returns the day of the puzzle.
+    #[must_use]
+    pub fn day(self) -> Day {
+        self.day
+    }
+
+    /// Returns the unix timestamp at which the puzzle unlocks, i.e. midnight in UTC-5.
+    #[must_use]
+    pub fn unlock_secs(self) -> i64 {
+        calendar::event_midnight(
+            i64::from(self.year.into_inner()),
+            12,
+            u32::from(self.day.into_inner()),
+        )
+    }
+
+    /// Returns the instant at which the puzzle unlocks.
+    #[must_use]
+    pub fn unlocks_at(self) -> SystemTime {
+        // every event starts after the unix epoch.
+        UNIX_EPOCH + Duration::from_secs(self.unlock_secs().unsigned_abs())
+    }
+
+    /// Returns `true` if the puzzle can be opened.
+    #[must_use]
+    pub fn is_unlocked(self) -> bool {
+        self.is_unlocked_at(calendar::now_secs())
+    }
+
+    /// Returns `true` if the puzzle can be opened at `unix_secs`.
+    #[must_use]
+    pub fn is_unlocked_at(self, unix_secs: i64) -> bool {
+        unix_secs >= self.unlock_secs()
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.year, self.day)
+    }
+}
+
+impl TryFrom<(Year, Day)> for PuzzleId {
+    type Error = PuzzleIdError;
+
+    fn try_from((year, day): (Year, Day)) -> Result<Self, Self::Error> {
+        Self::new(year, day).ok_or(PuzzleIdError::NotInEvent { year, day })
+    }
+}
+
+impl From<PuzzleId> for (Year, Day) {
+    fn from(id: PuzzleId) -> Self {
+        (id.year, id.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, template::get_year(), calendar::now_secs())
+    }
+}
+
+/// An error which can be returned when parsing or creating a [`PuzzleId`].
+#[derive(Debug)]
+pub enum PuzzleIdError {
+    Year(YearFromStrError),
+    Day(DayFromStrError),
+    NotInEvent { year: Year, day: Day },
+    NoDefaultYear,
+    NoPuzzleToday,
+}
+
+impl Error for PuzzleIdError {}
+
+impl Display for PuzzleIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleIdError::Year(e) => write!(f, "{e}"),
+            PuzzleIdError::Day(e) => write!(f, "{e}"),
+            PuzzleIdError::NotInEvent { year, day } => write!(
+                f,
+                "day {day} is not part of {year}, expecting a day number between 1 and {}",
+                year.day_count()
+            ),
+            PuzzleIdError::NoDefaultYear => {
+                f.write_str("no year given and no default_year set in aoc.toml or AOC_YEAR")
+            }
+            PuzzleIdError::NoPuzzleToday => f.write_str("no event is running today"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, PuzzleIdError};
+    use crate::calendar::event_midnight;
+    use crate::{day, year};
+
+    #[test]
+    fn validates_day_per_year() {
+        assert!(PuzzleId::new(year!(2023), day!(25)).is_some());
+        assert!(PuzzleId::new(year!(2025), day!(12)).is_some());
+        assert!(PuzzleId::new(year!(2025), day!(13)).is_none());
+        assert!(PuzzleId::try_from((year!(2025), day!(25))).is_err());
+    }
+
+    #[test]
+    fn parses_puzzle_ids() {
+        let now = event_midnight(2024, 12, 3);
+        let expected = PuzzleId::new(year!(2023), day!(5)).unwrap();
+
+        for s in ["2023/5", "2023-05", "2023_05", " 2023/05 "] {
+            assert_eq!(PuzzleId::parse(s, None, now).unwrap(), expected);
+        }
+        assert_eq!(
+            PuzzleId::parse("5", Some(year!(2023)), now).unwrap(),
+            expected
+        );
+        assert_eq!(
+            PuzzleId::parse("today", None, now).unwrap(),
+            PuzzleId::new(year!(2024), day!(3)).unwrap()
+        );
+
+        assert!(matches!(
+            PuzzleId::parse("5", None, now),
+            Err(PuzzleIdError::NoDefaultYear)
+        ));
+        assert!(matches!(
+            PuzzleId::parse("2025/1", None, now),
+            Err(PuzzleIdError::Year(_))
+        ));
+        assert_eq!(
+            PuzzleId::parse_upcoming("2025/1", None, now).unwrap(),
+            PuzzleId::new(year!(2025), day!(1)).unwrap()
+        );
+        assert!(matches!(
+            PuzzleId::parse_upcoming("2026/1", None, now),
+            Err(PuzzleIdError::Year(_))
+        ));
+        assert!(matches!(
+            PuzzleId::parse("2023/26", None, now),
+            Err(PuzzleIdError::Day(_))
+        ));
+        assert!(matches!(
+            PuzzleId::parse("today", None, event_midnight(2024, 12, 26)),
+            Err(PuzzleIdError::NoPuzzleToday)
+        ));
+    }
+
+    #[test]
+    fn knows_unlock_time() {
+        let id = PuzzleId::new(year!(2023), day!(1)).unwrap();
+        assert_eq!(id.unlock_secs(), 1_701_406_800);
+        assert!(!id.is_unlocked_at(1_701_406_799));
+        assert!(id.is_unlocked_at(1_701_406_800));
+        assert!(id.is_unlocked());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,133 @@
+/// Module that stores the accepted answers of each day in `data/answers/{year}_{day}.toml`.
+/// Known answers let the runner and the `verify` command detect regressions in solutions.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::config;
+use crate::{Day, Part, Year};
+
+/// The accepted answers of a single day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<String>,
+}
+
+/// The result of comparing an answer against the known answer of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Incorrect { expected: String },
+    Unknown,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Correct => f.write_str("✔"),
+            Check::Incorrect { expected } => write!(f, "✘ (expected {expected})"),
+            Check::Unknown => f.write_str("?"),
+        }
+    }
+}
+
+impl Answers {
+    /// Returns the known answer of `part`.
+    #[must_use]
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+
+    /// Sets the known answer of `part`.
+    pub fn set(&mut self, part: Part, answer: &str) {
+        match part {
+            Part::One => self.part_one = Some(answer.into()),
+            Part::Two => self.part_two = Some(answer.into()),
+        }
+    }
+
+    /// Compares `answer` against the known answer of `part`.
+    #[must_use]
+    pub fn check(&self, part: Part, answer: &str) -> Check {
+        match self.get(part) {
+            Some(expected) if expected == answer.trim() => Check::Correct,
+            Some(expected) => Check::Incorrect {
+                expected: expected.into(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_answers(year: Year, day: Day) -> PathBuf {
+    config::get()
+        .data_path("answers")
+        .join(format!("{year}_{day}.toml"))
+}
+
+/// Loads the known answers of a day. A missing file yields no answers.
+pub fn load(year: Year, day: Day) -> io::Result<Answers> {
+    let contents = match fs::read_to_string(get_path_for_answers(year, day)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+        Err(e) => return Err(e),
+    };
+
+    toml::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Records `answer` as the accepted answer of `part`.
+pub fn store(year: Year, day: Day, part: Part, answer: &str) -> io::Result<()> {
+    let mut answers = load(year, day)?;
+    answers.set(part, answer);
+
+    let path = get_path_for_answers(year, day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents =
+        toml::to_string(&answers).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, contents)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Check};
+    use crate::Part;
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers: Answers = toml::from_str("part_one = \"17141\"\n").unwrap();
+        assert_eq!(answers.get(Part::One), Some("17141"));
+        assert_eq!(answers.get(Part::Two), None);
+    }
+
+    #[test]
+    fn serializes_known_answers_only() {
+        let mut answers = Answers::default();
+        answers.set(Part::Two, "42");
+        assert_eq!(toml::to_string(&answers).unwrap(), "part_two = \"42\"\n");
+    }
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = Answers::default();
+        answers.set(Part::One, "42");
+        assert_eq!(answers.check(Part::One, "42"), Check::Correct);
+        assert_eq!(
+            answers.check(Part::One, "41"),
+            Check::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(Part::Two, "42"), Check::Unknown);
+    }
+}
@@ -0,0 +1,124 @@
+/// Facade over the backends that talk to the Advent of Code website.
+///
+/// The native [`AocClient`] is used by default. Set `AOC_BACKEND=aoc-cli` to shell out to the
+/// `aoc-cli` binary instead.
+use std::{env, fmt::Display, fs};
+
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::aoc_client::{AocClient, AocClientError, Verdict};
+use crate::{Day, Part, Year};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Native,
+    AocCli,
+}
+
+#[derive(Debug)]
+pub enum AocError {
+    Cli(AocCommandError),
+    Client(AocClientError),
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Cli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            AocError::Cli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            AocError::Client(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<AocCommandError> for AocError {
+    fn from(e: AocCommandError) -> Self {
+        AocError::Cli(e)
+    }
+}
+
+impl From<AocClientError> for AocError {
+    fn from(e: AocClientError) -> Self {
+        AocError::Client(e)
+    }
+}
+
+impl From<std::io::Error> for AocError {
+    fn from(e: std::io::Error) -> Self {
+        AocError::Client(AocClientError::IO(e))
+    }
+}
+
+/// Returns the backend selected by the `AOC_BACKEND` environment variable.
+#[must_use]
+pub fn backend() -> Backend {
+    match env::var("AOC_BACKEND").as_deref() {
+        Ok("aoc-cli") => Backend::AocCli,
+        _ => Backend::Native,
+    }
+}
+
+/// Checks that the selected backend can talk to the website, i.e. that `aoc-cli` is installed or
+/// that a session cookie is set.
+pub fn check() -> Result<(), AocError> {
+    if backend() == Backend::AocCli {
+        aoc_cli::check()?;
+        return Ok(());
+    }
+
+    AocClient::from_env()?;
+    Ok(())
+}
+
+/// Downloads input and puzzle description to the data directory.
+pub fn download(year: Year, day: Day) -> Result<(), AocError> {
+    if backend() == Backend::AocCli {
+        aoc_cli::check()?;
+        aoc_cli::download(year, day)?;
+        return Ok(());
+    }
+
+    let client = AocClient::from_env()?;
+    let input_path = aoc_cli::get_input_path(year, day);
+    let puzzle_path = aoc_cli::get_puzzle_path(year, day);
+
+    fs::write(&input_path, client.input(year, day)?)?;
+    fs::write(&puzzle_path, client.puzzle(year, day)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{input_path}\".");
+    println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+    Ok(())
+}
+
+/// Refreshes the puzzle description and prints it to the terminal.
+pub fn read(year: Year, day: Day) -> Result<(), AocError> {
+    if backend() == Backend::AocCli {
+        aoc_cli::check()?;
+        aoc_cli::read(year, day)?;
+        return Ok(());
+    }
+
+    let client = AocClient::from_env()?;
+    let puzzle = client.puzzle(year, day)?;
+    fs::write(aoc_cli::get_puzzle_path(year, day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// Submits an answer for one part of a puzzle.
+pub fn submit(year: Year, day: Day, part: Part, answer: &str) -> Result<Verdict, AocError> {
+    if backend() == Backend::AocCli {
+        aoc_cli::check()?;
+        let output = aoc_cli::submit(year, day, part, answer)?;
+        return Ok(Verdict::from_response(&String::from_utf8_lossy(
+            &output.stdout,
+        )));
+    }
+
+    Ok(AocClient::from_env()?.submit(year, day, part, answer)?)
+}
@@ -4,7 +4,8 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::{Day, Year};
+use crate::template::config;
+use crate::{Day, Part, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -27,6 +28,8 @@ impl Display for AocCommandError {
     }
 }
 
+impl std::error::Error for AocCommandError {}
+
 pub fn check() -> Result<(), AocCommandError> {
     Command::new("aoc")
         .arg("-V")
@@ -71,42 +74,54 @@ pub fn download(year: Year, day: Day) -> Result<Output, AocCommandError> {
 
     let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    println!("🎄 Successfully wrote input to \"{}\".", input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", puzzle_path);
     Ok(output)
 }
 
-pub fn submit(year: Year, day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer. The output is captured so that the response can be parsed, and echoed.
+pub fn submit(year: Year, day: Day, part: Part, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], year, day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(year: Year, day: Day) -> String {
-    format!("data/inputs/{year}_{day}.txt")
+    let output = Command::new("aoc")
+        .args(&args)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_puzzle_path(year: Year, day: Day) -> String {
-    format!("data/puzzles/{year}_{day}.md")
+#[must_use]
+pub fn get_input_path(year: Year, day: Day) -> String {
+    config::get()
+        .data_path("inputs")
+        .join(format!("{year}_{day}.txt"))
+        .display()
+        .to_string()
 }
 
-// fn get_year() -> Option<u16> {
-//     match std::env::var("AOC_YEAR") {
-//         Ok(x) => x.parse().ok().or(None),
-//         Err(_) => None,
-//     }
-// }
+#[must_use]
+pub fn get_puzzle_path(year: Year, day: Day) -> String {
+    config::get()
+        .data_path("puzzles")
+        .join(format!("{year}_{day}.md"))
+        .display()
+        .to_string()
+}
 
 fn build_args(command: &str, args: &[String], year: Year, day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    // if let Some(year) = get_year() {
-    //     cmd_args.push("--year".into());
-    //     cmd_args.push(year.to_string());
-    // }
-
     cmd_args.append(&mut vec![
         "--year".into(),
         year.to_string(),
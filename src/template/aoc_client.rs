@@ -0,0 +1,446 @@
+/// Native client for the Advent of Code website.
+///
+/// The session cookie is read from the `AOC_SESSION` environment variable or, like `aoc-cli` does,
+/// from `~/.adventofcode.session`. The base url defaults to the real website and can be overridden
+/// with `AOC_BASE_URL`, e.g. to run against a local stand-in server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, sync::OnceLock, time::Duration};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::template::config;
+use crate::{Day, Part, Year};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/ankarhem/advent-of-code)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Request(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION, session_file in aoc.toml or create ~/.adventofcode.session."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "could not read response: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Request(e.to_string())
+    }
+}
+
+/// The response of the website to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited { wait_secs: Option<u64> },
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the text of the page returned after submitting an answer.
+    #[must_use]
+    pub fn from_response(text: &str) -> Self {
+        static WAIT: OnceLock<Regex> = OnceLock::new();
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            let wait_secs = WAIT
+                .get_or_init(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap())
+                .captures(text)
+                .map(|c| {
+                    let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                    let seconds: u64 = c[2].parse().unwrap_or(0);
+                    minutes * 60 + seconds
+                });
+            Verdict::RateLimited { wait_secs }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::Incorrect => f.write_str("wrong"),
+            Verdict::TooHigh => f.write_str("wrong (too high)"),
+            Verdict::TooLow => f.write_str("wrong (too low)"),
+            Verdict::RateLimited {
+                wait_secs: Some(wait_secs),
+            } => write!(f, "rate-limited ({wait_secs}s left to wait)"),
+            Verdict::RateLimited { wait_secs: None } => f.write_str("rate-limited"),
+            Verdict::AlreadySolved => f.write_str("already solved or not unlocked"),
+            Verdict::Unknown => f.write_str("unknown response"),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Creates a client from the `AOC_BASE_URL` and `AOC_SESSION` environment variables,
+    /// falling back to the real website and the session file in the home directory.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Downloads the personal puzzle input.
+    pub fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(year, day));
+        Ok(self.get(&url)?.into_string()?)
+    }
+
+    /// Downloads the puzzle description and converts it to markdown.
+    pub fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(year, day))?.into_string()?;
+        Ok(html_to_markdown(&html))
+    }
+
+    /// Downloads a private leaderboard as JSON. The leaderboard of a user has the id of the user.
+    pub fn leaderboard(&self, year: Year, id: u64) -> Result<String, AocClientError> {
+        let url = format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            self.base_url
+        );
+        Ok(self.get(&url)?.into_string()?)
+    }
+
+    /// Submits an answer and parses the verdict of the website.
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: Part,
+        answer: &str,
+    ) -> Result<Verdict, AocClientError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        Ok(Verdict::from_response(&html_to_markdown(&html)))
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<ureq::Response, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?)
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let mut paths: Vec<_> = config::get().session_file.iter().cloned().collect();
+    if let Some(home) = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
+        paths.push(PathBuf::from(&home).join(".adventofcode.session"));
+        paths.push(
+            PathBuf::from(&home)
+                .join(".config")
+                .join("adventofcode.session"),
+        );
+    }
+
+    paths
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// Converts the puzzle articles (and the answers given so far) of a page to markdown,
+/// following the format `aoc-cli` writes puzzle files in.
+#[must_use]
+pub fn html_to_markdown(html: &str) -> String {
+    static SECTIONS: OnceLock<Regex> = OnceLock::new();
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+
+    let sections = SECTIONS.get_or_init(|| {
+        Regex::new(r"(?s)<article[^>]*>.*?</article>|<p>Your puzzle answer was.*?</p>").unwrap()
+    });
+    let tags =
+        TAGS.get_or_init(|| Regex::new(r#"<(/?)(\w+)(?:[^>]*?href="([^"]*)")?[^>]*>"#).unwrap());
+
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut heading_start = None;
+    let mut links: Vec<String> = vec![];
+
+    for section in sections.find_iter(html) {
+        let section = section.as_str();
+        let mut last = 0;
+
+        for tag in tags.captures_iter(section) {
+            let whole = tag.get(0).unwrap();
+            let text = &section[last..whole.start()];
+            // whitespace between block elements is not part of the content.
+            if in_pre || !text.trim().is_empty() {
+                markdown.push_str(&decode_entities(text));
+            }
+            last = whole.end();
+
+            let is_close = &tag[1] == "/";
+            match (&tag[2], is_close) {
+                ("h2", false) => heading_start = Some(markdown.len()),
+                ("h2", true) => {
+                    if let Some(start) = heading_start.take() {
+                        if markdown[start..].starts_with("---") {
+                            markdown.insert(start, '\\');
+                        }
+                    }
+                    markdown.push_str("\n----------\n\n");
+                }
+                ("p", true) => markdown.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    markdown.push_str("```\n\n");
+                }
+                ("em", _) if !in_pre => markdown.push('*'),
+                ("code", _) if !in_pre => markdown.push('`'),
+                ("li", false) => markdown.push_str("* "),
+                ("li", true) | ("br", _) => markdown.push('\n'),
+                ("ul", true) => markdown.push('\n'),
+                ("a", false) => {
+                    links.push(tag.get(3).map_or(String::new(), |m| m.as_str().into()));
+                    markdown.push('[');
+                }
+                ("a", true) => {
+                    let href = links.pop().unwrap_or_default();
+                    markdown.push_str(&format!("]({href})"));
+                }
+                _ => {}
+            }
+        }
+
+        markdown.push_str(&decode_entities(&section[last..]));
+    }
+
+    markdown.trim_end().to_string() + "\n"
+}
+
+/// Decodes the HTML entities that puzzle descriptions use.
+pub(crate) fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, AocClient, Verdict};
+    use crate::{day, year, Part};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serves a single request with `body` and sends the raw request back through the channel.
+    fn serve_once(body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, rx) = serve_once("1\n2\n3\n");
+        let client = AocClient::new(&base_url, "secret");
+
+        let input = client.input(year!(2023), day!(8)).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/8/input HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, rx) = serve_once(
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        let verdict = client
+            .submit(year!(2023), day!(8), Part::Two, "42")
+            .unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/8/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait."
+            ),
+            Verdict::RateLimited {
+                wait_secs: Some(65)
+            }
+        );
+        assert_eq!(
+            Verdict::from_response("You don't seem to be solving the right level."),
+            Verdict::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 8: Haunted Wasteland ---</h2><p>You reach <code>ZZZ</code> in <code><em>2</em></code> steps. See <a href="/2023/about">about</a>.</p>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+</code></pre>
+<ul><li>a &lt; b</li></ul>
+</article>
+<p>Your puzzle answer was <code>17141</code>.</p>
+</main>"#;
+
+        let expected = [
+            r"\--- Day 8: Haunted Wasteland ---",
+            "----------",
+            "",
+            "You reach `ZZZ` in `*2*` steps. See [about](/2023/about).",
+            "",
+            "```",
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "```",
+            "",
+            "* a < b",
+            "",
+            "Your puzzle answer was `17141`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(html), expected);
+    }
+}
@@ -0,0 +1,271 @@
+/// Module that persists benchmark results so that they can be compared across runs.
+/// Every timed run appends one JSON record per part to `data/benchmarks/{year}_{day}.jsonl`.
+use std::{
+    env, fs,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::config;
+use crate::template::report::PartReport;
+use crate::{Day, Part, Year};
+
+/// Name of the environment variable that holds an optional label for the current run.
+pub const LABEL_ENV: &str = "AOC_BENCH_LABEL";
+
+/// A single benchmark result of one part (or the parse step) of a solution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Output of `git describe --always --dirty` at the time of the run.
+    pub commit: Option<String>,
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub report: PartReport,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for `report`, stamped with the current time, commit and label.
+    #[must_use]
+    pub fn new(report: PartReport) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            commit: current_commit(),
+            label: env::var(LABEL_ENV).ok().filter(|l| !l.is_empty()),
+            report,
+        }
+    }
+
+    /// Whether this entry was recorded at the commit or with the label `name`.
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        self.label.as_deref() == Some(name)
+            || self.commit.as_deref().is_some_and(|c| c.starts_with(name))
+    }
+}
+
+/// The change of one part's median time between a baseline and the latest run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: Year,
+    pub day: Day,
+    pub part: Option<Part>,
+    pub baseline_nanos: u64,
+    pub latest_nanos: u64,
+    /// Relative change in percent, positive values mean the part got slower.
+    pub change: f64,
+}
+
+impl Comparison {
+    /// Whether the part got slower by more than `threshold` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change > threshold
+    }
+}
+
+#[must_use]
+pub fn get_path_for_history(year: Year, day: Day) -> PathBuf {
+    config::get()
+        .data_path("benchmarks")
+        .join(format!("{year}_{day}.jsonl"))
+}
+
+/// Appends `entry` to the history file of its day.
+pub fn append(entry: &HistoryEntry) -> io::Result<()> {
+    let path = get_path_for_history(entry.report.year, entry.report.day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Loads every recorded entry of a day, oldest first. A missing file yields no entries.
+pub fn load(year: Year, day: Day) -> io::Result<Vec<HistoryEntry>> {
+    let contents = match fs::read_to_string(get_path_for_history(year, day)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(io::Error::from))
+        .collect()
+}
+
+/// Compares the latest entry of every part against a baseline.
+///
+/// The baseline is the most recent earlier entry matching `baseline` (see [`HistoryEntry::matches`]),
+/// or the previous entry of the part if no baseline is given. Parts without a baseline are skipped.
+#[must_use]
+pub fn compare(entries: &[HistoryEntry], baseline: Option<&str>) -> Vec<Comparison> {
+    let mut parts: Vec<_> = entries.iter().map(|e| e.report.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    parts
+        .into_iter()
+        .filter_map(|part| {
+            let mut history = entries.iter().filter(|e| e.report.part == part).rev();
+            let latest = history.next()?;
+            let base = match baseline {
+                Some(name) => history.find(|e| e.matches(name))?,
+                None => history.next()?,
+            };
+
+            let (baseline_nanos, latest_nanos) =
+                (base.report.median_nanos, latest.report.median_nanos);
+
+            #[allow(clippy::cast_precision_loss)]
+            let change = if baseline_nanos == 0 {
+                0_f64
+            } else {
+                (latest_nanos as f64 - baseline_nanos as f64) / baseline_nanos as f64 * 100_f64
+            };
+
+            Some(Comparison {
+                year: latest.report.year,
+                day: latest.report.day,
+                part,
+                baseline_nanos,
+                latest_nanos,
+                change,
+            })
+        })
+        .collect()
+}
+
+/// Returns the report of the latest entry of every part, the parse step first.
+#[must_use]
+pub fn latest(entries: &[HistoryEntry]) -> Vec<PartReport> {
+    let mut reports: Vec<PartReport> = vec![];
+
+    for entry in entries.iter().rev() {
+        if !reports.iter().any(|r| r.part == entry.report.part) {
+            reports.push(entry.report.clone());
+        }
+    }
+
+    reports.sort_by_key(|r| r.part);
+    reports
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, latest, HistoryEntry};
+    use crate::template::report::PartReport;
+    use crate::{day, year, Part};
+
+    fn entry(
+        part: Option<Part>,
+        median_nanos: u64,
+        commit: &str,
+        label: Option<&str>,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 0,
+            commit: Some(commit.into()),
+            label: label.map(Into::into),
+            report: PartReport::mock(year!(2023), day!(9), part, None, median_nanos),
+        }
+    }
+
+    #[test]
+    fn compares_against_previous_run() {
+        let entries = [
+            entry(Some(Part::One), 100, "aaaaaaa", None),
+            entry(Some(Part::Two), 100, "aaaaaaa", None),
+            entry(Some(Part::One), 150, "bbbbbbb", None),
+            entry(Some(Part::Two), 90, "bbbbbbb", None),
+        ];
+
+        let comparisons = compare(&entries, None);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].part, Some(Part::One));
+        assert!((comparisons[0].change - 50_f64).abs() < 1e-6);
+        assert!(comparisons[0].is_regression(10_f64));
+        assert!((comparisons[1].change + 10_f64).abs() < 1e-6);
+        assert!(!comparisons[1].is_regression(10_f64));
+    }
+
+    #[test]
+    fn compares_against_named_baseline() {
+        let entries = [
+            entry(Some(Part::One), 100, "aaaaaaa", Some("before-refactor")),
+            entry(Some(Part::One), 300, "bbbbbbb", None),
+            entry(Some(Part::One), 105, "ccccccc", None),
+        ];
+
+        let by_label = compare(&entries, Some("before-refactor"));
+        assert_eq!(by_label[0].baseline_nanos, 100);
+        assert!(!by_label[0].is_regression(10_f64));
+
+        let by_commit = compare(&entries, Some("bbb"));
+        assert_eq!(by_commit[0].baseline_nanos, 300);
+    }
+
+    #[test]
+    fn skips_parts_without_baseline() {
+        let entries = [
+            entry(None, 100, "aaaaaaa", None),
+            entry(None, 100, "bbbbbbb", None),
+            entry(Some(Part::One), 100, "bbbbbbb", None),
+        ];
+
+        let comparisons = compare(&entries, None);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, None);
+        assert!(compare(&entries, Some("unknown")).is_empty());
+    }
+
+    #[test]
+    fn finds_latest_runs() {
+        let entries = [
+            entry(Some(Part::Two), 100, "aaaaaaa", None),
+            entry(None, 10, "aaaaaaa", None),
+            entry(Some(Part::One), 50, "aaaaaaa", None),
+            entry(Some(Part::Two), 90, "bbbbbbb", None),
+        ];
+
+        let reports = latest(&entries);
+        assert_eq!(
+            reports
+                .iter()
+                .map(|r| (r.part, r.median_nanos))
+                .collect::<Vec<_>>(),
+            [(None, 10), (Some(Part::One), 50), (Some(Part::Two), 90)]
+        );
+    }
+}
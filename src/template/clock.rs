@@ -0,0 +1,28 @@
+/// Module that provides the current time to commands that wait for a puzzle to unlock.
+/// Tests pass a fake [`Clock`] instead of waiting for real.
+use std::{thread, time::Duration};
+
+use crate::calendar;
+
+/// A source of the current time that can also wait.
+pub trait Clock {
+    /// Returns the current time in seconds since the unix epoch.
+    fn now_secs(&self) -> i64;
+
+    /// Blocks for `duration`.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_secs(&self) -> i64 {
+        calendar::now_secs()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
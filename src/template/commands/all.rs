@@ -1,39 +1,121 @@
-use std::io;
+use std::{fmt::Display, io, process};
+
+use itertools::Itertools;
 
 use crate::template::{
+    options::RunOptions,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry,
+    report::{Outcome, PartReport},
+    scaffolded_solutions, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Part, Year};
+
+/// Runs every scaffolded solution of `year`, or of every year if `year` is [`None`].
+/// If `part` is set, only that part of every solution runs.
+/// Parts that run longer than `timeout_secs` (or the configured timeout) are abandoned.
+pub fn handle(
+    year: Option<Year>,
+    is_release: bool,
+    is_timed: bool,
+    part: Option<Part>,
+    timeout_secs: Option<u64>,
+) {
+    let mut years: Vec<Year> = scaffolded_solutions()
+        .into_iter()
+        .map(|(year, _)| year)
+        .dedup()
+        .collect();
+
+    if let Some(year) = year {
+        years.retain(|y| *y == year);
+        if years.is_empty() {
+            println!("No solutions found for {year}.");
+            return;
+        }
+    }
+
+    let options = RunOptions {
+        time: is_timed,
+        part,
+        timeout_secs,
+        ..RunOptions::default()
+    };
+
+    if let Err(e) = options.validate() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut failures: Vec<PartReport> = vec![];
+    let mut errors: Vec<String> = vec![];
+    let mut total_millis = 0_f64;
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    for (i, year) in years.iter().enumerate() {
+        if i > 0 {
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        println!("{ANSI_BOLD}{year}{ANSI_RESET}");
+        println!("====");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let mut year_timings: Vec<Timings> = vec![];
+
+        year.days().for_each(|day| {
+            println!();
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            match child_commands::run_solution(*year, day, &options, is_release, false) {
+                Ok(Some(reports)) => {
+                    year_timings.push(child_commands::to_timings(&reports, *year, day));
+                    failures.extend(reports.into_iter().filter(|r| !r.outcome.is_completed()));
+                }
+                Ok(None) => println!("Not solved."),
+                Err(e) => {
+                    eprintln!("Failed to run {year} day {day}: {e}");
+                    errors.push(format!("{year}/{day}: {e}"));
+                }
+            }
+        });
+
+        if is_timed {
+            let year_millis =
+                year_timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+            total_millis += year_millis;
+
+            println!(
+                "\n{ANSI_BOLD}Total {year}:{ANSI_RESET} {ANSI_ITALIC}{year_millis:.2}ms{ANSI_RESET}"
+            );
         }
-    });
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+        timings.extend(year_timings);
+    }
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+    print_failures(&failures, &errors);
+
+    if is_timed && registry::has_abandoned() {
+        println!(
+            "Timings after a timeout may be too slow: solutions that timed out in-process keep \
+             running until all solutions finished."
+        );
+    }
+
+    if is_timed {
+        if years.len() > 1 {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            if let Some(part) = part {
+                println!("Skipped README benchmarks: only part {part} was run.");
+                return;
+            }
+
+            match readme_benchmarks::update(timings) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -43,149 +125,183 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
+/// Lists the parts that panicked or timed out and the days that failed to run, after the output
+/// of every day.
+fn print_failures(failures: &[PartReport], errors: &[String]) {
+    if failures.is_empty() && errors.is_empty() {
+        return;
+    }
+
+    let count = |f: fn(&Outcome) -> bool| failures.iter().filter(|r| f(&r.outcome)).count();
+    let panics = count(|o| matches!(o, Outcome::Panicked { .. }));
+    let timeouts = count(|o| matches!(o, Outcome::TimedOut { .. }));
+
+    println!(
+        "\n{ANSI_BOLD}Failures:{ANSI_RESET} {panics} panicked, {timeouts} timed out, {} failed to run",
+        errors.len()
+    );
+    for r in failures {
+        let step = r
+            .part
+            .map_or_else(|| "parse".to_string(), |part| format!("part {part}"));
+        println!("  {}/{} {step}: {}", r.year, r.day, r.outcome);
+    }
+    for e in errors {
+        println!("  {e}");
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
     Parser(String),
+    /// The solution stopped without reporting why, e.g. because it did not compile.
+    Failed(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "{s}"),
+            Error::Failed(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "could not run solution: {e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the reports they emit.
+/// Solutions in the [`registry`] are run in-process instead.
+pub(crate) mod child_commands {
+    use super::Error;
+    use crate::template::options::RunOptions;
+    use crate::template::report::{self, PartReport, REPORT_FILE_ENV};
+    use crate::template::{get_path_for_bin, readme_benchmarks::Timings, registry};
+    use crate::{Day, Part, Year};
     use std::{
-        io::{BufRead, BufReader},
+        env, fs,
         path::Path,
-        process::{Command, Stdio},
-        thread,
+        process::{self, Command, Stdio},
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given year and day with `options`, optionally discarding its
+    /// output. Returns [`None`] if the day has not been scaffolded yet.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        options: &RunOptions,
+        is_release: bool,
+        is_quiet: bool,
+    ) -> Result<Option<Vec<PartReport>>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // registered solutions run in-process, in the build profile of the running executable.
+        if let Some(solution) = registry::find(year, day) {
+            let reports = registry::run(solution, options, is_quiet);
+            if reports.is_empty() {
+                return Err(Error::Failed(format!(
+                    "{year}_{day} stopped before reporting a part"
+                )));
+            }
+            return Ok(Some(reports));
+        }
+
+        let bin_name = format!("{year}_{day}");
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".into(),
+            "--bin".into(),
+            bin_name.clone(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        // mirror options like `--time` to child invocations.
+        args.push("--".into());
+        args.extend(options.to_args());
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let report_path =
+            env::temp_dir().join(format!("aoc-report-{}-{bin_name}.jsonl", process::id()));
+        // a stale file would mix reports of previous runs into this one.
+        let _ = fs::remove_file(&report_path);
 
         let mut cmd = Command::new("cargo")
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .env(REPORT_FILE_ENV, &report_path)
+            .stdout(if is_quiet {
+                Stdio::null()
+            } else {
+                Stdio::inherit()
+            })
+            .stderr(Stdio::inherit())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let status = cmd.wait()?;
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let reports = report::read(&report_path)
+            .map_err(|e| Error::Parser(format!("could not read reports: {e}")))?;
+        let _ = fs::remove_file(&report_path);
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        // solutions also exit with an error after reporting a part that panicked or timed out.
+        if !status.success() && reports.iter().all(|r| r.outcome.is_completed()) {
+            return Err(Error::Failed(format!("{bin_name} exited with {status}")));
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        Ok(Some(reports))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
+    /// Converts the reports of a solution binary into README timings.
+    pub fn to_timings(reports: &[PartReport], year: Year, day: Day) -> Timings {
+        let mut timings = Timings {
+            year,
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            reports: reports
+                .iter()
+                .filter(|r| r.outcome.is_completed())
+                .cloned()
+                .collect(),
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        for r in reports {
+            let median = Duration::from_nanos(r.median_nanos);
+            let timing_str = if r.samples > 1 {
+                let std_dev = Duration::from_nanos(r.std_dev_nanos);
+                format!("{median:.1?} ± {std_dev:.1?}")
+            } else {
+                format!("{median:.1?}")
+            };
+
+            match (r.part, &r.answer) {
+                // the parse step has no answer but counts towards the total.
+                (None, _) => {}
+                (Some(Part::One), Some(_)) => timings.part_1 = Some(timing_str),
+                (Some(Part::Two), Some(_)) => timings.part_2 = Some(timing_str),
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = r.median_nanos as f64;
+            timings.total_nanos += nanos;
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -202,48 +318,73 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::to_timings;
 
-        use crate::day;
+        use crate::template::report::PartReport;
+        use crate::{day, year, Part};
+
+        fn report(part: Option<Part>, answer: Option<&str>, median_nanos: u64) -> PartReport {
+            PartReport::mock(year!(2023), day!(1), part, answer, median_nanos)
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let res = to_timings(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(Some(Part::One), Some("0"), 74),
+                    report(Some(Part::Two), Some("10"), 74_130_000),
                 ],
+                year!(2023),
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns ± 0.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms ± 741.3µs");
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
+        fn test_patterns_in_answers() {
+            let res = to_timings(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(
+                        Some(Part::One),
+                        Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                        2_000_000_000,
+                    ),
+                    report(Some(Part::Two), Some("10s"), 100_000_000),
                 ],
+                year!(2023),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s ± 20.0ms");
+            assert_eq!(res.part_2.unwrap(), "100.0ms ± 1.0ms");
+        }
+
+        #[test]
+        fn test_parse_step() {
+            let res = to_timings(
+                &[
+                    report(None, None, 1_000),
+                    report(Some(Part::One), Some("1"), 2_000),
+                    report(Some(Part::Two), Some("2"), 3_000),
+                ],
+                year!(2023),
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 6000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0µs ± 20.0ns");
+            assert_eq!(res.part_2.unwrap(), "3.0µs ± 30.0ns");
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
+            let res = to_timings(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(Some(Part::One), None, 10),
+                    report(Some(Part::Two), None, 10),
                 ],
+                year!(2023),
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
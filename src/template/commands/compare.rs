@@ -0,0 +1,77 @@
+use std::{process, time::Duration};
+
+use crate::template::{
+    benchmark_history::{self, Comparison},
+    scaffolded_solutions, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::Year;
+
+/// Compares the latest benchmark of every part against a baseline and exits with a non-zero
+/// status if any part got slower than `threshold` percent.
+pub fn handle(year: Option<Year>, baseline: Option<&str>, threshold: f64) {
+    let mut compared = 0;
+    let mut regressions = 0;
+
+    for (year, day) in scaffolded_solutions()
+        .into_iter()
+        .filter(|(y, _)| year.is_none_or(|year| *y == year))
+    {
+        let entries = match benchmark_history::load(year, day) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Failed to read benchmark history of {year} day {day}: {e}");
+                continue;
+            }
+        };
+
+        let comparisons = benchmark_history::compare(&entries, baseline);
+        if comparisons.is_empty() {
+            continue;
+        }
+
+        if compared > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}{year} Day {day}{ANSI_RESET}");
+        println!("-----------");
+
+        for comparison in comparisons {
+            let is_regression = comparison.is_regression(threshold);
+            if is_regression {
+                regressions += 1;
+            }
+            compared += 1;
+            println!("{}", format_comparison(&comparison, is_regression));
+        }
+    }
+
+    if compared == 0 {
+        println!("No benchmarks to compare. Run `cargo time` to record some.");
+        return;
+    }
+
+    println!();
+    if regressions > 0 {
+        println!(
+            "{ANSI_BOLD}{regressions} regression(s){ANSI_RESET} slower than {ANSI_ITALIC}{threshold}%{ANSI_RESET}."
+        );
+        process::exit(1);
+    }
+
+    println!("No regressions slower than {ANSI_ITALIC}{threshold}%{ANSI_RESET}.");
+}
+
+fn format_comparison(comparison: &Comparison, is_regression: bool) -> String {
+    let name = match comparison.part {
+        Some(part) => format!("Part {part}"),
+        None => "Parse".into(),
+    };
+
+    let baseline = Duration::from_nanos(comparison.baseline_nanos);
+    let latest = Duration::from_nanos(comparison.latest_nanos);
+    let change = comparison.change;
+
+    let marker = if is_regression { " ⚠" } else { "" };
+    format!("{name}: {baseline:.1?} → {latest:.1?} ({change:+.1}%){marker}")
+}
@@ -0,0 +1,111 @@
+use std::{fs, io, path::Path, process};
+
+use crate::template::commands::scaffold::{default_tests, render_tests, ExampleInput, ExampleTest};
+use crate::template::examples::{self, get_path_for_example, Example};
+use crate::template::get_path_for_bin;
+use crate::{Day, Year};
+
+pub fn handle(year: Year, day: Day) {
+    let examples = match examples::load(year, day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("Failed to read puzzle description: {e}");
+            process::exit(1);
+        }
+    };
+
+    if examples.is_empty() {
+        println!("No examples found in the puzzle description.");
+        return;
+    }
+
+    let tests = match write_examples(year, day, &examples) {
+        Ok(tests) => tests,
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    };
+
+    let module_path = get_path_for_bin(year, day);
+    let Ok(module) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let placeholder = render_tests(&default_tests());
+    if !module.contains(&placeholder) {
+        println!("Tests in \"{module_path}\" were already changed, leaving them as is");
+        return;
+    }
+
+    for test in &tests {
+        let Some(answer) = &test.answer else {
+            continue;
+        };
+        match test.input {
+            ExampleInput::Numbered(n) => {
+                println!("Expecting {answer} for part {} of example {n}", test.part);
+            }
+            _ => println!("Expecting {answer} for part {} of the example", test.part),
+        }
+    }
+
+    let module = module.replace(&placeholder, &render_tests(&tests));
+    if let Err(e) = fs::write(&module_path, module) {
+        eprintln!("Failed to update module file: {e}");
+        process::exit(1);
+    }
+}
+
+/// Writes the inputs of `examples` to their example files and returns one test per example.
+///
+/// Example files that already have content are left as is. If their content differs from the
+/// extracted input, the expected answer of the example is dropped from its test.
+pub fn write_examples(year: Year, day: Day, examples: &[Example]) -> io::Result<Vec<ExampleTest>> {
+    let numbers = examples::file_numbers(examples);
+    let mut written: Vec<(Option<u8>, String)> = vec![];
+
+    examples
+        .iter()
+        .zip(numbers)
+        .map(|(example, number)| {
+            let contents = match written.iter().find(|(n, _)| *n == number) {
+                Some((_, contents)) => contents.clone(),
+                None => {
+                    let contents =
+                        write_example(&get_path_for_example(year, day, number), &example.input)?;
+                    written.push((number, contents.clone()));
+                    contents
+                }
+            };
+
+            Ok(ExampleTest {
+                part: example.part,
+                input: number.map_or(ExampleInput::Shared, ExampleInput::Numbered),
+                answer: example.answer.clone().filter(|_| contents == example.input),
+            })
+        })
+        .collect()
+}
+
+/// Writes `input` to an empty or missing example file and returns the example file's contents.
+fn write_example(path: &Path, input: &str) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(existing) if !existing.trim().is_empty() => {
+            println!(
+                "Example file \"{}\" is not empty, leaving it as is",
+                path.display()
+            );
+            Ok(existing)
+        }
+        Ok(_) => write_new_example(path, input),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => write_new_example(path, input),
+        Err(e) => Err(e),
+    }
+}
+
+fn write_new_example(path: &Path, input: &str) -> io::Result<String> {
+    fs::write(path, input)?;
+    println!("Wrote example to \"{}\"", path.display());
+    Ok(input.into())
+}
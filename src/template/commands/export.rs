@@ -0,0 +1,58 @@
+use std::{fs, path::Path, process};
+
+use super::all::child_commands;
+use crate::template::{
+    benchmark_history,
+    export::{self, ExportFormat},
+    readme_benchmarks::Timings,
+    scaffolded_solutions,
+};
+use crate::Year;
+
+/// Exports the latest recorded benchmarks of `year`, or of every year if [`None`], to `output` or
+/// to stdout.
+pub fn handle(year: Option<Year>, format: ExportFormat, output: Option<&Path>) {
+    let mut timings: Vec<Timings> = vec![];
+
+    for (year, day) in scaffolded_solutions()
+        .into_iter()
+        .filter(|(y, _)| year.is_none_or(|year| *y == year))
+    {
+        match benchmark_history::load(year, day) {
+            Ok(entries) if !entries.is_empty() => {
+                let reports = benchmark_history::latest(&entries);
+                timings.push(child_commands::to_timings(&reports, year, day));
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to read benchmark history of {year} day {day}: {e}"),
+        }
+    }
+
+    if timings.is_empty() {
+        eprintln!("No benchmarks to export. Run `cargo time` to record some.");
+        process::exit(1);
+    }
+
+    let contents = match export::render(&timings, format) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Failed to export benchmarks: {e}");
+            process::exit(1);
+        }
+    };
+
+    match output {
+        Some(path) => match fs::write(path, contents) {
+            Ok(()) => println!(
+                "Exported benchmarks of {} days to {}.",
+                timings.len(),
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("Failed to write {}: {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => print!("{contents}"),
+    }
+}
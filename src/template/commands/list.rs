@@ -0,0 +1,32 @@
+use itertools::Itertools;
+
+use crate::template::{registry, scaffolded_solutions};
+use crate::Year;
+
+pub fn handle(year: Option<Year>) {
+    let solutions = scaffolded_solutions();
+    let in_process = !registry::solutions().is_empty();
+
+    for (y, days) in &solutions
+        .into_iter()
+        .filter(|(y, _)| year.is_none_or(|year| year == *y))
+        .group_by(|(y, _)| *y)
+    {
+        let days = days
+            .map(|(_, day)| {
+                if !in_process || registry::find(y, day).is_some() {
+                    day.to_string()
+                } else {
+                    // scaffolded after the executable was built, runs through cargo.
+                    format!("{day}*")
+                }
+            })
+            .join(" ");
+        println!("{y}: {days}");
+    }
+
+    if in_process {
+        println!("---");
+        println!("Solutions run in-process. Days marked with * are not compiled into this executable yet.");
+    }
+}
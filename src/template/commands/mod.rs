@@ -1,5 +1,13 @@
 pub mod all;
+pub mod compare;
 pub mod download;
+pub mod examples;
+pub mod export;
+pub mod list;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
+pub mod verify;
+pub mod wait;
@@ -0,0 +1,92 @@
+use std::{fs, path::Path, process};
+
+use crate::template::aoc_client::AocClient;
+use crate::template::progress::{self, Leaderboard, Progress, ProgressError};
+use crate::Year;
+
+/// Collects the stars of `year`, or of every year if [`None`], and writes them to the README.
+/// Stars come from the local answers and submissions, a leaderboard file and, if `sync` is set,
+/// the private leaderboards of the user on the website.
+pub fn handle(year: Option<Year>, sync: bool, leaderboard: Option<&Path>) {
+    match collect(year, sync, leaderboard) {
+        Ok(progress) => write(&progress),
+        Err(e) => {
+            eprintln!("Failed to collect progress: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn collect(
+    year: Option<Year>,
+    sync: bool,
+    leaderboard: Option<&Path>,
+) -> Result<Progress, ProgressError> {
+    let mut progress = Progress::load_local(year)?;
+    let user_id = progress::get_user_id();
+
+    if let Some(path) = leaderboard {
+        let leaderboard = Leaderboard::parse(&fs::read_to_string(path)?)?;
+        let event = leaderboard.year()?;
+        if year.is_none_or(|year| year == event) {
+            progress.add_leaderboard(&leaderboard, user_id)?;
+        } else {
+            println!("Skipped leaderboard of {event}.");
+        }
+    }
+
+    if sync {
+        let client = AocClient::from_env()?;
+        let id = user_id.ok_or_else(|| {
+            ProgressError::Leaderboard("set user_id in aoc.toml or AOC_USER_ID to sync".into())
+        })?;
+
+        for year in sync_years(year, &progress) {
+            let synced = client
+                .leaderboard(year, id)
+                .map_err(ProgressError::from)
+                .and_then(|json| Leaderboard::parse(&json))
+                .and_then(|leaderboard| progress.add_leaderboard(&leaderboard, Some(id)));
+
+            if let Err(e) = synced {
+                eprintln!("Skipped leaderboard of {year}: {e}");
+            }
+        }
+    }
+
+    Ok(progress)
+}
+
+/// The years to sync: `year` if set, or else the years that have local stars or a table in the
+/// README, so that years without any progress cost no requests.
+fn sync_years(year: Option<Year>, progress: &Progress) -> Vec<Year> {
+    if let Some(year) = year {
+        return vec![year];
+    }
+
+    let readme_years = progress::readme_years().unwrap_or_else(|e| {
+        eprintln!("Failed to read years of README: {e:?}");
+        vec![]
+    });
+
+    let mut years: Vec<Year> = progress.years().chain(readme_years).collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+fn write(progress: &Progress) {
+    if progress.years().next().is_none() {
+        println!("No stars found.");
+        return;
+    }
+
+    for year in progress.years() {
+        println!("{year}: {} ⭐", progress.count(year));
+    }
+
+    match progress::update_readme(progress) {
+        Ok(()) => println!("Successfully updated README with progress."),
+        Err(_) => eprintln!("Failed to update readme with progress."),
+    }
+}
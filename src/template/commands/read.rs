@@ -1,16 +1,16 @@
 use std::process;
 
-use crate::template::aoc_cli;
-use crate::{Day, Year};
+use crate::template::aoc;
+use crate::PuzzleId;
 
-pub fn handle(year: Year, day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: PuzzleId) {
+    if !puzzle.is_unlocked() {
+        eprintln!("Puzzle {puzzle} is not unlocked yet.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(year, day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = aoc::read(puzzle.year(), puzzle.day()) {
+        eprintln!("failed to read puzzle: {e}");
         process::exit(1);
     };
 }
@@ -1,11 +1,18 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::{Day, Year};
+use crate::template::aoc_cli::get_input_path;
+use crate::template::commands::examples::write_examples;
+use crate::template::examples::{self, get_path_for_example, ExampleFile};
+use crate::template::{config, get_path_for_bin};
+use crate::{Day, Part, Year};
 
+/// The built-in module template. A custom template can be configured with `template` in `aoc.toml`;
+/// it is filled in the same way, replacing `YEAR_NUMBER`, `DAY_NUMBER` and `TESTS`.
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -19,60 +26,215 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+TESTS}
+"#;
 
+const TEST_TEMPLATE: &str = r#"
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
-        assert_eq!(None, result);
+    fn TEST_NAME() {
+        let result = PART_FN(&advent_of_code::template::READ_CALL);
+        assert_eq!(EXPECTED, result);
     }
+"#;
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
-        assert_eq!(None, result);
-    }
+/// Where the input of a scaffolded test is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleInput {
+    /// The example file shared by both parts, e.g. `2023_08.txt`.
+    Shared,
+    /// A numbered example file, e.g. `2023_08-2.txt`.
+    Numbered(u8),
+    /// The example file of a single part, e.g. `2023_08_part2.txt`.
+    Part,
+}
+
+/// A scaffolded test that runs one part against an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleTest {
+    pub part: Part,
+    pub input: ExampleInput,
+    pub answer: Option<String>,
+}
+
+/// The tests of a day without known examples: both parts against the shared example file.
+#[must_use]
+pub fn default_tests() -> Vec<ExampleTest> {
+    Part::ALL
+        .into_iter()
+        .map(|part| ExampleTest {
+            part,
+            input: ExampleInput::Shared,
+            answer: None,
+        })
+        .collect()
+}
+
+/// Renders one test function per example test.
+/// Parts with more than one example get numbered test names, e.g. `test_part_one_example_2`.
+#[must_use]
+pub fn render_tests(tests: &[ExampleTest]) -> String {
+    tests
+        .iter()
+        .map(|test| {
+            let part_name = match test.part {
+                Part::One => "one",
+                Part::Two => "two",
+            };
+            let examples_of_part: Vec<_> = tests.iter().filter(|t| t.part == test.part).collect();
+
+            let test_name = if examples_of_part.len() > 1 {
+                let n = examples_of_part
+                    .iter()
+                    .position(|t| std::ptr::eq(*t, test))
+                    .unwrap_or_default();
+                format!("test_part_{part_name}_example_{}", n + 1)
+            } else {
+                format!("test_part_{part_name}")
+            };
+
+            let read_call = match test.input {
+                ExampleInput::Shared => "read_file(\"examples\", YEAR, DAY)".to_string(),
+                ExampleInput::Numbered(n) => {
+                    format!("read_file_numbered(\"examples\", YEAR, DAY, {n})")
+                }
+                ExampleInput::Part => {
+                    format!(
+                        "read_file_part(\"examples\", YEAR, DAY, advent_of_code::Part::{:?})",
+                        test.part
+                    )
+                }
+            };
+
+            let expected = match &test.answer {
+                Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+                Some(answer) => format!("Some({answer:?})"),
+                None => "None".into(),
+            };
+
+            TEST_TEMPLATE
+                .replace("TEST_NAME", &test_name)
+                .replace("PART_FN", &format!("part_{part_name}"))
+                .replace("READ_CALL", &read_call)
+                .replace("EXPECTED", &expected)
+        })
+        .collect()
+}
+
+/// Builds tests for the example files that already exist for a day.
+fn tests_for_files(files: &[ExampleFile]) -> Vec<ExampleTest> {
+    let mut tests: Vec<_> = files
+        .iter()
+        .flat_map(|file| {
+            let input = match (file.number, file.part) {
+                (Some(n), _) => ExampleInput::Numbered(n),
+                (None, Some(_)) => ExampleInput::Part,
+                (None, None) => ExampleInput::Shared,
+            };
+            let parts = match file.part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+            parts.into_iter().map(move |part| ExampleTest {
+                part,
+                input,
+                answer: None,
+            })
+        })
+        .collect();
+
+    tests.sort_by_key(|t| t.part);
+    tests
 }
-"#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(year: Year, day: Day) {
-    let input_path = format!("data/inputs/{year}_{day}.txt");
-    let example_path = format!("data/examples/{year}_{day}.txt");
-    let module_path = format!("src/bin/{year}_{day}.rs");
+/// Sets up the examples of a new day and returns the tests to scaffold for them.
+///
+/// Examples are taken from the downloaded puzzle description if there is one, or else from the
+/// existing example files. Without either, an empty example file is created.
+fn scaffold_examples(year: Year, day: Day) -> Vec<ExampleTest> {
+    match examples::load(year, day) {
+        Ok(found) if !found.is_empty() => match write_examples(year, day, &found) {
+            Ok(tests) => return tests,
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        },
+        _ => {}
+    }
+
+    let files = examples::list(year, day);
+    if !files.is_empty() {
+        return tests_for_files(&files);
+    }
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    let example_path = get_path_for_example(year, day, None).display().to_string();
+    match create_file(&example_path) {
+        Ok(_) => {
+            println!("Created empty example file \"{}\"", example_path);
+        }
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
+    }
+
+    default_tests()
+}
+
+pub fn handle(year: Year, day: Day) {
+    let input_path = get_input_path(year, day);
+    let module_path = get_path_for_bin(year, day);
+
+    // the module file is created last, so that a failure leaves no half-written solution behind.
+    if Path::new(&module_path).exists() {
+        eprintln!("Failed to create module file: \"{module_path}\" already exists");
+        process::exit(1);
+    }
+
+    let template = match &config::get().template {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(template) => template,
+            Err(e) => {
+                eprintln!("Failed to read module template \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => MODULE_TEMPLATE.to_string(),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .replace("YEAR_NUMBER", &year.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let tests = scaffold_examples(year, day);
+    let contents = template
+        .replace("DAY_NUMBER", &day.into_inner().to_string())
+        .replace("YEAR_NUMBER", &year.into_inner().to_string())
+        .replace("TESTS", &render_tests(&tests));
+
+    let written =
+        safe_create_file(&module_path).and_then(|mut file| file.write_all(contents.as_bytes()));
+    match written {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path);
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
     }
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path);
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -80,19 +242,42 @@ pub fn handle(year: Year, day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
-
     println!("---");
     println!(
         "🎄 Type `cargo solve {} {}` to run your solution.",
         year, day
     );
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{default_tests, render_tests, ExampleInput, ExampleTest};
+    use crate::Part;
+
+    #[test]
+    fn renders_default_tests() {
+        let rendered = render_tests(&default_tests());
+        assert!(rendered.contains("fn test_part_one() {\n        let result = part_one(&advent_of_code::template::read_file(\"examples\", YEAR, DAY));\n        assert_eq!(None, result);"));
+        assert!(rendered.contains("fn test_part_two()"));
+    }
+
+    #[test]
+    fn renders_one_test_per_example() {
+        let test = |part, input, answer: &str| ExampleTest {
+            part,
+            input,
+            answer: Some(answer.into()),
+        };
+        let rendered = render_tests(&[
+            test(Part::One, ExampleInput::Numbered(1), "2"),
+            test(Part::One, ExampleInput::Numbered(2), "6"),
+            test(Part::Two, ExampleInput::Part, "ABC"),
+        ]);
+
+        assert!(rendered.contains("fn test_part_one_example_1()"));
+        assert!(rendered.contains("fn test_part_one_example_2()"));
+        assert!(rendered.contains("read_file_numbered(\"examples\", YEAR, DAY, 2));\n        assert_eq!(Some(6), result);"));
+        assert!(rendered.contains("fn test_part_two()"));
+        assert!(rendered.contains("read_file_part(\"examples\", YEAR, DAY, advent_of_code::Part::Two));\n        assert_eq!(Some(\"ABC\"), result);"));
+    }
+}
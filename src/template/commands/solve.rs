@@ -1,8 +1,19 @@
 use std::process::{Command, Stdio};
 
+use crate::template::options::{Format, RunOptions};
+use crate::template::{registry, report};
 use crate::{Day, Year};
 
-pub fn handle(year: Year, day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+pub fn handle(year: Year, day: Day, release: bool, options: &RunOptions) {
+    if let Some(solution) = registry::find(year, day) {
+        let is_json = options.format == Format::Json;
+        let reports = registry::run(solution, options, is_json);
+        if is_json {
+            report::print_json(&reports);
+        }
+        return;
+    }
+
     let binary = format!("{year}_{day}");
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), binary]; // day.to_string()
 
@@ -11,15 +22,7 @@ pub fn handle(year: Year, day: Day, release: bool, time: bool, submit_part: Opti
     }
 
     cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
-
-    if time {
-        cmd_args.push("--time".to_string());
-    }
+    cmd_args.extend(options.to_args());
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
@@ -0,0 +1,261 @@
+use std::{path::Path, time::Duration};
+
+use itertools::Itertools;
+
+use crate::template::{
+    answers, aoc_cli, benchmark_history, config, examples, get_path_for_bin, parse_bin_name,
+    scaffolded_solutions, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_ITALIC, ANSI_RESET, ANSI_YELLOW,
+};
+use crate::{Day, Part, PuzzleId, Year};
+
+/// Number of days per row of the calendar.
+const COLUMNS: usize = 5;
+/// Visible width of a day in the calendar.
+const CELL_WIDTH: usize = 13;
+
+/// What is known about a single day of an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DayStatus {
+    day: Day,
+    is_unlocked: bool,
+    is_scaffolded: bool,
+    has_input: bool,
+    has_example: bool,
+    has_puzzle: bool,
+    /// Whether the answer of each part is known.
+    answers: [bool; 2],
+    /// Sum of the medians of the latest benchmark of every part.
+    benchmark_nanos: Option<u64>,
+}
+
+impl DayStatus {
+    fn load(year: Year, day: Day) -> Self {
+        let known = answers::load(year, day).unwrap_or_default();
+        let benchmark_nanos = benchmark_history::load(year, day)
+            .ok()
+            .filter(|entries| !entries.is_empty())
+            .map(|entries| {
+                benchmark_history::latest(&entries)
+                    .iter()
+                    .map(|r| r.median_nanos)
+                    .sum()
+            });
+
+        Self {
+            day,
+            is_unlocked: PuzzleId::new(year, day).is_some_and(PuzzleId::is_unlocked),
+            is_scaffolded: Path::new(&get_path_for_bin(year, day)).exists(),
+            has_input: Path::new(&aoc_cli::get_input_path(year, day)).exists(),
+            has_example: !examples::list(year, day).is_empty(),
+            has_puzzle: Path::new(&aoc_cli::get_puzzle_path(year, day)).exists(),
+            answers: Part::ALL.map(|part| known.get(part).is_some()),
+            benchmark_nanos,
+        }
+    }
+}
+
+/// Prints a calendar of `year`, or of every year with solutions or inputs if [`None`].
+pub fn handle(year: Option<Year>) {
+    let years: Vec<Year> = match year {
+        Some(year) => vec![year],
+        None => years_in_progress(),
+    };
+
+    if years.is_empty() {
+        println!("Nothing scaffolded or downloaded yet.");
+        return;
+    }
+
+    for (i, year) in years.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let days: Vec<DayStatus> = year.days().map(|day| DayStatus::load(year, day)).collect();
+        print!("{}", render_year(year, &days));
+    }
+
+    println!();
+    println!(
+        "{ANSI_GREEN}★{ANSI_RESET} known answer  {ANSI_GREEN}S{ANSI_RESET}caffolded  \
+         {ANSI_GREEN}I{ANSI_RESET}nput  {ANSI_GREEN}E{ANSI_RESET}xample  \
+         {ANSI_GREEN}P{ANSI_RESET}uzzle  {ANSI_YELLOW}{ANSI_ITALIC}time{ANSI_RESET} of the last benchmark"
+    );
+}
+
+/// Returns the years with scaffolded solutions or downloaded inputs.
+fn years_in_progress() -> Vec<Year> {
+    let inputs = std::fs::read_dir(config::get().data_path("inputs"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| parse_bin_name(entry.ok()?.path().file_stem()?.to_str()?));
+
+    scaffolded_solutions()
+        .into_iter()
+        .chain(inputs)
+        .map(|(year, _)| year)
+        .sorted()
+        .dedup()
+        .collect()
+}
+
+/// Renders the calendar of a year, with [`COLUMNS`] days per row.
+fn render_year(year: Year, days: &[DayStatus]) -> String {
+    let solved = days
+        .iter()
+        .map(|d| d.answers.iter().filter(|known| **known).count())
+        .sum::<usize>();
+
+    let mut out =
+        format!("{ANSI_BOLD}{year}{ANSI_RESET} {ANSI_DIM}({solved} ★){ANSI_RESET}\n====\n");
+
+    for row in days.chunks(COLUMNS) {
+        let cells: Vec<[String; 3]> = row.iter().map(render_day).collect();
+        for i in 0..3 {
+            let line = cells.iter().map(|cell| pad(&cell[i])).join(" ");
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Renders the three lines of a day: its number and stars, its files and its last benchmark.
+fn render_day(status: &DayStatus) -> [String; 3] {
+    let day = status.day;
+    if !status.is_unlocked {
+        return [
+            format!("{ANSI_DIM}{day}{ANSI_RESET}"),
+            String::new(),
+            String::new(),
+        ];
+    }
+
+    let flag = |text: &str, is_set: bool| {
+        if is_set {
+            format!("{ANSI_GREEN}{text}{ANSI_RESET}")
+        } else {
+            format!("{ANSI_DIM}{text}{ANSI_RESET}")
+        }
+    };
+
+    let stars = status.answers.map(|known| flag("★", known)).join("");
+    let header = if status.answers.iter().all(|known| *known) {
+        format!("{ANSI_BOLD}{day}{ANSI_RESET} {stars}")
+    } else {
+        format!("{day} {stars}")
+    };
+
+    let files = [
+        flag("S", status.is_scaffolded),
+        flag("I", status.has_input),
+        flag("E", status.has_example),
+        flag("P", status.has_puzzle),
+    ]
+    .join(" ");
+
+    let benchmark = match status.benchmark_nanos {
+        Some(nanos) => format!(
+            "{ANSI_YELLOW}{ANSI_ITALIC}{:.1?}{ANSI_RESET}",
+            Duration::from_nanos(nanos)
+        ),
+        None => format!("{ANSI_DIM}-{ANSI_RESET}"),
+    };
+
+    [header, files, benchmark]
+}
+
+/// Pads a line to [`CELL_WIDTH`] visible characters, not counting ANSI escape sequences.
+fn pad(line: &str) -> String {
+    let padding = CELL_WIDTH.saturating_sub(visible_len(line));
+    format!("{line}{}", " ".repeat(padding))
+}
+
+fn visible_len(line: &str) -> usize {
+    visible_chars(line).count()
+}
+
+/// Returns the characters of `line` without its ANSI escape sequences.
+fn visible_chars(line: &str) -> impl Iterator<Item = char> + '_ {
+    line.chars()
+        .scan(false, |in_escape, c| {
+            let is_visible = match (*in_escape, c) {
+                (false, '\x1b') => {
+                    *in_escape = true;
+                    false
+                }
+                (false, _) => true,
+                (true, 'm') => {
+                    *in_escape = false;
+                    false
+                }
+                (true, _) => false,
+            };
+            Some(is_visible.then_some(c))
+        })
+        .flatten()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_year, visible_chars, visible_len, DayStatus};
+    use crate::{year, Day};
+
+    fn status(day: u8, answers: [bool; 2], benchmark_nanos: Option<u64>) -> DayStatus {
+        DayStatus {
+            day: Day::new(day).unwrap(),
+            is_unlocked: day <= 7,
+            is_scaffolded: answers[0],
+            has_input: answers[0],
+            has_example: true,
+            has_puzzle: false,
+            answers,
+            benchmark_nanos,
+        }
+    }
+
+    #[test]
+    fn measures_visible_width() {
+        assert_eq!(
+            visible_chars("\x1b[1m01\x1b[0m \x1b[32m★\x1b[0m").collect::<String>(),
+            "01 ★"
+        );
+        assert_eq!(visible_len("\x1b[1m01\x1b[0m ★"), 4);
+        assert_eq!(visible_len(""), 0);
+    }
+
+    #[test]
+    fn renders_calendar_rows() {
+        let days: Vec<DayStatus> = (1..=12)
+            .map(|day| match day {
+                1 => status(day, [true, true], Some(1_500_000)),
+                2 => status(day, [true, false], None),
+                _ => status(day, [false, false], None),
+            })
+            .collect();
+
+        let rendered: String = visible_chars(&render_year(year!(2025), &days)).collect();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "2025 (3 ★)");
+        assert_eq!(
+            lines[2],
+            "01 ★★         02 ★★         03 ★★         04 ★★         05 ★★"
+        );
+        assert_eq!(
+            lines[3].split_whitespace().take(4).collect::<Vec<_>>(),
+            ["S", "I", "E", "P"]
+        );
+        assert!(lines[4].starts_with("1.5ms         -"));
+        // locked days only show their number.
+        assert_eq!(
+            lines[6],
+            "06 ★★         07 ★★         08            09            10"
+        );
+        // 12 days take three rows.
+        assert_eq!(rendered.matches("11").count(), 1);
+        assert_eq!(lines.len(), 2 + 3 * 4);
+    }
+}
@@ -0,0 +1,173 @@
+use std::process;
+
+use itertools::Itertools;
+
+use super::all::child_commands;
+use crate::template::{
+    answers::{self, Answers, Check},
+    options::RunOptions,
+    report::PartReport,
+    scaffolded_solutions, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{Part, Year};
+
+/// The verification outcome of a single part.
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Missing,
+}
+
+/// Runs every scaffolded solution against its real input and compares the results with the known
+/// answers. Exits with a non-zero status if any part does not match its known answer.
+pub fn handle(year: Option<Year>) {
+    let solutions: Vec<_> = scaffolded_solutions()
+        .into_iter()
+        .filter(|(y, _)| year.is_none_or(|year| *y == year))
+        .collect();
+
+    if solutions.is_empty() {
+        println!("No solutions to verify.");
+        return;
+    }
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for (i, (year, days)) in solutions
+        .iter()
+        .group_by(|(year, _)| *year)
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}{year}{ANSI_RESET}");
+        println!("====");
+
+        for (_, day) in days {
+            let reports = match child_commands::run_solution(
+                year,
+                *day,
+                &RunOptions::default(),
+                true,
+                true,
+            ) {
+                Ok(reports) => reports.unwrap_or_default(),
+                Err(e) => {
+                    eprintln!("Failed to run {year} day {day}: {e}");
+                    vec![]
+                }
+            };
+
+            let known = answers::load(year, *day).unwrap_or_else(|e| {
+                eprintln!("Failed to read known answers of {year} day {day}: {e}");
+                Answers::default()
+            });
+
+            let statuses = Part::ALL.map(|part| verify_part(&known, &reports, part));
+
+            let line = statuses
+                .iter()
+                .zip(1..)
+                .map(|(status, part)| match status {
+                    Status::Pass => {
+                        passed += 1;
+                        format!("Part {part}: ✔")
+                    }
+                    Status::Fail { expected, actual } => {
+                        failed += 1;
+                        let actual = actual.as_deref().unwrap_or("✖");
+                        format!("Part {part}: ✘ (expected {expected}, got {actual})")
+                    }
+                    Status::Missing => {
+                        missing += 1;
+                        format!("Part {part}: {ANSI_ITALIC}no known answer{ANSI_RESET}")
+                    }
+                })
+                .join(" | ");
+
+            println!("Day {day} | {line}");
+        }
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed, {missing} without known answer."
+    );
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn verify_part(known: &Answers, reports: &[PartReport], part: Part) -> Status {
+    let Some(expected) = known.get(part) else {
+        return Status::Missing;
+    };
+
+    let actual = reports
+        .iter()
+        .find(|r| r.part == Some(part))
+        .and_then(|r| r.answer.clone());
+
+    // compares like the runner does, so that `solve` and `verify` agree.
+    match actual {
+        Some(actual) if known.check(part, &actual) == Check::Correct => Status::Pass,
+        actual => Status::Fail {
+            expected: expected.into(),
+            actual,
+        },
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify_part, Status};
+    use crate::template::{answers::Answers, report::PartReport};
+    use crate::{day, year, Part};
+
+    fn report(part: Part, answer: Option<&str>) -> PartReport {
+        PartReport::mock(year!(2023), day!(1), Some(part), answer, 1)
+    }
+
+    #[test]
+    fn verifies_parts() {
+        let mut known = Answers::default();
+        known.set(Part::One, "42");
+        known.set(Part::Two, "7");
+
+        let reports = [report(Part::One, Some("42")), report(Part::Two, Some("8"))];
+        assert_eq!(verify_part(&known, &reports, Part::One), Status::Pass);
+        assert_eq!(
+            verify_part(&known, &[report(Part::One, Some("42\n"))], Part::One),
+            Status::Pass
+        );
+        assert_eq!(
+            verify_part(&known, &reports, Part::Two),
+            Status::Fail {
+                expected: "7".into(),
+                actual: Some("8".into())
+            }
+        );
+    }
+
+    #[test]
+    fn reports_missing_answers() {
+        let mut known = Answers::default();
+        known.set(Part::One, "42");
+
+        assert_eq!(verify_part(&known, &[], Part::Two), Status::Missing);
+        assert_eq!(
+            verify_part(&known, &[report(Part::One, None)], Part::One),
+            Status::Fail {
+                expected: "42".into(),
+                actual: None
+            }
+        );
+    }
+}
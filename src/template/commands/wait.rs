@@ -0,0 +1,226 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+    process,
+    time::Duration,
+};
+
+use crate::template::aoc::{self, AocError};
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::aoc_client::AocClientError;
+use crate::template::clock::{Clock, SystemClock};
+use crate::template::commands::{read, scaffold};
+use crate::template::get_path_for_bin;
+use crate::PuzzleId;
+
+/// Number of download attempts before giving up.
+const MAX_ATTEMPTS: u32 = 8;
+/// Delay before the first retry, doubled after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Scaffolds a puzzle, waits for it to unlock, then downloads and prints it.
+pub fn handle(puzzle: PuzzleId) {
+    let (year, day) = (puzzle.year(), puzzle.day());
+
+    if let Err(e) = aoc::check() {
+        eprintln!("failed to download puzzle: {e}");
+        process::exit(1);
+    }
+
+    if !Path::new(&get_path_for_bin(year, day)).exists() {
+        scaffold::handle(year, day);
+    }
+
+    let clock = SystemClock;
+    let waited = countdown(puzzle, &clock, |remaining| {
+        print!(
+            "\r⏳ Puzzle {puzzle} unlocks in {}",
+            format_remaining(remaining)
+        );
+        let _ = io::stdout().flush();
+    });
+    if waited {
+        println!("\r🔓 Puzzle {puzzle} is unlocked.            ");
+    }
+
+    let downloaded = retry(
+        &clock,
+        || aoc::download(year, day),
+        |e, delay| eprintln!("Download failed ({e}), retrying in {}s.", delay.as_secs()),
+    );
+    if let Err(e) = downloaded {
+        eprintln!("failed to download puzzle: {e}");
+        process::exit(1);
+    }
+
+    read::handle(puzzle);
+}
+
+/// Sleeps until `puzzle` unlocks, calling `on_tick` with the remaining seconds about once per
+/// second. Returns `true` if the puzzle was still locked.
+pub fn countdown(puzzle: PuzzleId, clock: &impl Clock, mut on_tick: impl FnMut(u64)) -> bool {
+    let mut waited = false;
+
+    loop {
+        let remaining = puzzle.unlock_secs() - clock.now_secs();
+        if remaining <= 0 {
+            return waited;
+        }
+        waited = true;
+        on_tick(remaining.unsigned_abs());
+        clock.sleep(Duration::from_secs(1));
+    }
+}
+
+/// Calls `f` until it succeeds, sleeping with exponential backoff between attempts.
+/// Errors that a retry cannot fix, e.g. a missing session cookie, are returned right away.
+pub fn retry<T>(
+    clock: &impl Clock,
+    mut f: impl FnMut() -> Result<T, AocError>,
+    mut on_retry: impl FnMut(&AocError, Duration),
+) -> Result<T, AocError> {
+    let mut delay = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= MAX_ATTEMPTS || !is_retryable(&e) => return Err(e),
+            Err(e) => {
+                on_retry(&e, delay);
+                clock.sleep(delay);
+                delay = (delay * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+fn is_retryable(e: &AocError) -> bool {
+    !matches!(
+        e,
+        AocError::Client(AocClientError::MissingSession)
+            | AocError::Cli(AocCommandError::CommandNotFound)
+    )
+}
+
+/// Formats a number of seconds as `HH:MM:SS`, prefixed with the days if there are any.
+fn format_remaining(secs: u64) -> String {
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+    let time = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{cell::RefCell, time::Duration};
+
+    use super::{countdown, format_remaining, retry, Clock, MAX_ATTEMPTS};
+    use crate::template::aoc::AocError;
+    use crate::template::aoc_client::AocClientError;
+    use crate::{day, year, PuzzleId};
+
+    /// A clock that advances by the slept duration instead of blocking.
+    struct FakeClock {
+        now: RefCell<i64>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: i64) -> Self {
+            Self {
+                now: RefCell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now_secs(&self) -> i64 {
+            *self.now.borrow()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.now.borrow_mut() += i64::try_from(duration.as_secs()).unwrap();
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn request_error() -> AocError {
+        AocError::Client(AocClientError::Request("404 Not Found".into()))
+    }
+
+    #[test]
+    fn counts_down_to_unlock() {
+        let puzzle = PuzzleId::new(year!(2023), day!(1)).unwrap();
+        let clock = FakeClock::at(puzzle.unlock_secs() - 3);
+
+        let mut ticks = vec![];
+        assert!(countdown(puzzle, &clock, |remaining| ticks.push(remaining)));
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now_secs(), puzzle.unlock_secs());
+
+        assert!(!countdown(puzzle, &clock, |_| panic!("already unlocked")));
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(0);
+        let mut calls = 0;
+        let result = retry(
+            &clock,
+            || {
+                calls += 1;
+                if calls < 4 {
+                    Err(request_error())
+                } else {
+                    Ok(calls)
+                }
+            },
+            |_, _| {},
+        );
+        assert_eq!(result.unwrap(), 4);
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [2, 4, 8].map(Duration::from_secs).to_vec()
+        );
+
+        let mut calls = 0;
+        let result: Result<(), _> = retry(
+            &clock,
+            || {
+                calls += 1;
+                Err(request_error())
+            },
+            |_, _| {},
+        );
+        assert!(result.is_err());
+        assert_eq!(calls, MAX_ATTEMPTS);
+
+        let mut calls = 0;
+        let result: Result<(), _> = retry(
+            &clock,
+            || {
+                calls += 1;
+                Err(AocError::Client(AocClientError::MissingSession))
+            },
+            |_, _| {},
+        );
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(59), "00:00:59");
+        assert_eq!(format_remaining(3723), "01:02:03");
+        assert_eq!(format_remaining(90_061), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,188 @@
+/// Module that loads the settings of the template from `aoc.toml` in the project root.
+/// Every setting is optional, a missing file yields the defaults.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+};
+
+use serde::Deserialize;
+
+use crate::Year;
+
+/// Name of the configuration file, relative to the project root.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Directory that holds inputs, examples, puzzles, answers and benchmarks.
+    pub data_dir: PathBuf,
+    /// Year used by commands that are not given one.
+    pub default_year: Option<Year>,
+    /// File that holds the session cookie, checked before the files in the home directory.
+    pub session_file: Option<PathBuf>,
+    /// Id of the user on the website, whose stars `progress` reads from private leaderboards.
+    pub user_id: Option<u64>,
+    /// README that the benchmark table is written to.
+    pub readme: PathBuf,
+    /// Module template used by `scaffold` instead of the built-in one.
+    pub template: Option<PathBuf>,
+    pub benchmark: BenchmarkConfig,
+    pub run: RunConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchmarkConfig {
+    /// Lower bound of the number of samples taken by `--time`.
+    pub min_samples: u128,
+    /// Upper bound of the number of samples taken by `--time`.
+    pub max_samples: u128,
+    /// Slowdown in percent above which `compare` reports a regression.
+    pub regression_threshold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    /// Seconds a part may run before it is abandoned, unless overridden by `--timeout`.
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::from("data"),
+            default_year: None,
+            session_file: None,
+            user_id: None,
+            readme: PathBuf::from("README.md"),
+            template: None,
+            benchmark: BenchmarkConfig::default(),
+            run: RunConfig::default(),
+        }
+    }
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            min_samples: 10,
+            max_samples: 10000,
+            regression_threshold: 10.0,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the path of a directory inside the data directory, e.g. `data/inputs`.
+    #[must_use]
+    pub fn data_path(&self, folder: &str) -> PathBuf {
+        self.data_dir.join(folder)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::IO(e) => write!(f, "could not read config: {e}"),
+            ConfigError::Parse(e) => write!(f, "could not parse config: {e}"),
+            ConfigError::Invalid(s) => write!(f, "invalid config: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Loads a config file. A missing file yields the default config.
+pub fn load(path: &Path) -> Result<Config, ConfigError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(ConfigError::IO(e)),
+    }
+}
+
+fn parse(contents: &str) -> Result<Config, ConfigError> {
+    let config: Config = toml::from_str(contents).map_err(ConfigError::Parse)?;
+
+    if config.benchmark.min_samples == 0
+        || config.benchmark.min_samples > config.benchmark.max_samples
+    {
+        return Err(ConfigError::Invalid(
+            "benchmark.min_samples must be positive and at most benchmark.max_samples".into(),
+        ));
+    }
+
+    if config.run.timeout_secs == Some(0) {
+        return Err(ConfigError::Invalid(
+            "run.timeout_secs must be positive".into(),
+        ));
+    }
+
+    Ok(config)
+}
+
+/// Returns the config of the project, loading [`CONFIG_FILE`] on first use.
+/// Exits the process if the file is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| match load(Path::new(CONFIG_FILE)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load {CONFIG_FILE}: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse, Config, ConfigError};
+    use crate::year;
+
+    #[test]
+    fn defaults_missing_settings() {
+        assert_eq!(parse("").unwrap(), Config::default());
+
+        let config = parse("default_year = 2021\n[benchmark]\nmax_samples = 500\n").unwrap();
+        assert_eq!(config.default_year, Some(year!(2021)));
+        assert_eq!(config.data_dir, PathBuf::from("data"));
+        assert_eq!(config.benchmark.min_samples, 10);
+        assert_eq!(config.benchmark.max_samples, 500);
+        assert_eq!(config.run.timeout_secs, None);
+
+        let config = parse("[run]\ntimeout_secs = 30\n").unwrap();
+        assert_eq!(config.run.timeout_secs, Some(30));
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        assert!(matches!(
+            parse("default_year = 1999\n"),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(parse("year = 2023\n"), Err(ConfigError::Parse(_))));
+        assert!(matches!(
+            parse("[benchmark]\nmin_samples = 100\nmax_samples = 10\n"),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse("[run]\ntimeout_secs = 0\n"),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+}
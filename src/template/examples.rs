@@ -0,0 +1,423 @@
+/// Module that extracts the examples and their expected answers from downloaded puzzle descriptions.
+/// The descriptions are the markdown files in `data/puzzles`, as written by the `download` command.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::aoc_client::decode_entities;
+use crate::template::config;
+use crate::{Day, Part, Year};
+
+/// The example of one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: Part,
+    pub input: String,
+    /// The expected answer for `input`, if the description states one.
+    pub answer: Option<String>,
+}
+
+/// An example file of a day in `data/examples`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleFile {
+    pub path: PathBuf,
+    /// The number of a numbered file, e.g. `2` for `2023_08-2.txt`.
+    pub number: Option<u8>,
+    /// The part of a per-part file, e.g. [`Part::Two`] for `2023_08_part2.txt`.
+    pub part: Option<Part>,
+}
+
+/// Returns the path of the unnumbered or a numbered example file of a day.
+#[must_use]
+pub fn get_path_for_example(year: Year, day: Day, number: Option<u8>) -> PathBuf {
+    let name = match number {
+        Some(n) => format!("{year}_{day}-{n}.txt"),
+        None => format!("{year}_{day}.txt"),
+    };
+    config::get().data_path("examples").join(name)
+}
+
+/// Lists the example files of a day: the shared file, numbered files and per-part files.
+#[must_use]
+pub fn list(year: Year, day: Day) -> Vec<ExampleFile> {
+    let Ok(entries) = fs::read_dir(config::get().data_path("examples")) else {
+        return vec![];
+    };
+
+    let mut files: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            let (number, part) = parse_example_name(path.file_stem()?.to_str()?, year, day)?;
+            Some(ExampleFile { path, number, part })
+        })
+        .collect();
+
+    files.sort_unstable_by_key(|f| (f.part, f.number));
+    files
+}
+
+/// Parses the stem of an example file name into its number and part.
+fn parse_example_name(stem: &str, year: Year, day: Day) -> Option<(Option<u8>, Option<Part>)> {
+    let rest = stem.strip_prefix(&format!("{year}_{day}"))?;
+
+    if rest.is_empty() {
+        Some((None, None))
+    } else if let Some(number) = rest.strip_prefix('-') {
+        Some((Some(number.parse().ok()?), None))
+    } else if let Some(part) = rest.strip_prefix("_part") {
+        Some((None, Some(part.parse().ok()?)))
+    } else {
+        None
+    }
+}
+
+/// Extracts the examples of every part from a puzzle description.
+///
+/// An example is a code block following a paragraph that introduces an example, and its answer is
+/// the last emphasized code span (`` `*42*` ``) before the next example. A part without an example
+/// of its own reuses the last input of the previous part.
+#[must_use]
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+
+    for (part, section) in Part::ALL.into_iter().zip(split_parts(markdown)) {
+        let (found, answer) = find_examples(section);
+
+        if found.is_empty() {
+            if let Some(previous) = examples.last() {
+                examples.push(Example {
+                    part,
+                    input: previous.input.clone(),
+                    answer,
+                });
+            }
+            continue;
+        }
+
+        examples.extend(found.into_iter().map(|(input, answer)| Example {
+            part,
+            input,
+            answer,
+        }));
+    }
+
+    examples
+}
+
+/// Extracts the examples of a day from its downloaded puzzle description.
+pub fn load(year: Year, day: Day) -> io::Result<Vec<Example>> {
+    fs::read_to_string(get_puzzle_path(year, day)).map(|md| extract(&md))
+}
+
+/// Returns the number of the example file of every example, in the same order.
+///
+/// Examples that share an input share a file. If all examples share one input, it is stored in the
+/// unnumbered example file of the day.
+#[must_use]
+pub fn file_numbers(examples: &[Example]) -> Vec<Option<u8>> {
+    let mut inputs: Vec<&str> = vec![];
+    let positions: Vec<_> = examples
+        .iter()
+        .map(|e| match inputs.iter().position(|i| *i == e.input) {
+            Some(i) => i,
+            None => {
+                inputs.push(&e.input);
+                inputs.len() - 1
+            }
+        })
+        .collect();
+
+    if inputs.len() <= 1 {
+        return vec![None; examples.len()];
+    }
+
+    positions
+        .into_iter()
+        .map(|i| u8::try_from(i + 1).ok())
+        .collect()
+}
+
+/// Splits a description into the sections of part one and part two.
+fn split_parts(markdown: &str) -> Vec<&str> {
+    let section = match markdown.find("--- Day") {
+        Some(start) => &markdown[start..],
+        None => markdown,
+    };
+
+    let sections = match section.find("--- Part Two ---") {
+        Some(split) => vec![&section[..split], &section[split..]],
+        None => vec![section],
+    };
+
+    sections
+        .into_iter()
+        .map(|s| match s.find("Your puzzle answer was") {
+            Some(end) => &s[..end],
+            None => s,
+        })
+        .collect()
+}
+
+/// Finds the example blocks of a section and the answer that belongs to each of them.
+/// If the section has no example block, the last answer of the section is returned on its own.
+fn find_examples(section: &str) -> (Vec<(String, Option<String>)>, Option<String>) {
+    let mut lines = section.lines();
+    let mut examples = vec![];
+    let mut example = None;
+    let mut answer = None;
+    // The answer as it was before the paragraph that introduced the upcoming block.
+    let mut answer_before_intro = None;
+    let mut answer_in_intro = None;
+    let mut introduces_example = false;
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        let block = if trimmed.starts_with("```") {
+            Some(read_fenced_block(&mut lines))
+        } else {
+            trimmed
+                .strip_prefix("<pre><code>")
+                .map(|rest| read_html_block(rest, &mut lines))
+        };
+
+        match block {
+            Some(block) if introduces_example && !block.trim().is_empty() => {
+                if let Some(previous) = example.replace(block) {
+                    examples.push((previous, answer_before_intro.take()));
+                }
+                // An introduction like "here is one that takes `*6*` steps" already states the answer.
+                answer = answer_in_intro.take();
+                introduces_example = false;
+            }
+            Some(_) => {}
+            None if !trimmed.is_empty() => {
+                introduces_example = is_introduction(trimmed);
+                answer_before_intro = answer.clone();
+                answer_in_intro = find_emphasized_code(trimmed);
+                answer = answer_in_intro.clone().or(answer);
+            }
+            None => {}
+        }
+    }
+
+    match example {
+        Some(example) => {
+            examples.push((example, answer));
+            (examples, None)
+        }
+        None => (examples, answer),
+    }
+}
+
+/// Whether a paragraph introduces a new example, rather than referring back to a previous one.
+fn is_introduction(paragraph: &str) -> bool {
+    let paragraph = paragraph.to_lowercase();
+    paragraph.contains("example") && !paragraph.contains("above") && !paragraph.contains("again")
+}
+
+fn read_fenced_block<'a>(lines: &mut impl Iterator<Item = &'a str>) -> String {
+    let block: Vec<_> = lines.take_while(|l| !l.trim().starts_with("```")).collect();
+    finish_block(&block.join("\n"))
+}
+
+fn read_html_block<'a>(first: &str, lines: &mut impl Iterator<Item = &'a str>) -> String {
+    let mut block = String::new();
+    let mut line = first;
+
+    loop {
+        if let Some(end) = line.find("</code></pre>") {
+            block.push_str(&line[..end]);
+            break;
+        }
+        block.push_str(line);
+        block.push('\n');
+
+        match lines.next() {
+            Some(next) => line = next,
+            None => break,
+        }
+    }
+
+    finish_block(&decode_entities(&strip_tags(&block)))
+}
+
+/// Trims blank lines around a block and terminates it with a single newline.
+fn finish_block(block: &str) -> String {
+    let mut block = block.trim_matches('\n').to_string();
+    block.push('\n');
+    block
+}
+
+/// Returns the last emphasized code span of a paragraph, written as `` `*42*` ``, `` *`42`* `` or
+/// `<code><em>42</em></code>`.
+fn find_emphasized_code(paragraph: &str) -> Option<String> {
+    if let Some(end) = paragraph.rfind("</em></code>") {
+        let start = paragraph[..end].rfind("<code><em>")? + "<code><em>".len();
+        return Some(decode_entities(&paragraph[start..end]));
+    }
+
+    let segments: Vec<_> = paragraph.split('`').collect();
+
+    // Segments with odd indices are the contents of code spans.
+    (1..segments.len().saturating_sub(1))
+        .step_by(2)
+        .rev()
+        .find_map(|i| {
+            let code = segments[i];
+            if code.len() > 2 && code.starts_with('*') && code.ends_with('*') {
+                Some(code[1..code.len() - 1].to_string())
+            } else if segments[i - 1].ends_with('*') && segments[i + 1].starts_with('*') {
+                Some(code.to_string())
+            } else {
+                None
+            }
+        })
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, file_numbers, parse_example_name, Example};
+    use crate::{day, year, Part};
+
+    const PUZZLE: &str = "\\--- Day 1: Trebuchet?! ---
+----------
+
+Some introduction with a diagram.
+
+```
+not an example
+```
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+
+```
+
+In this example, the values are `12` and `38`. Adding these together produces `*50*`.
+
+Your puzzle answer was `54390`.
+
+\\--- Part Two ---
+----------
+
+For example:
+
+```
+two1nine
+
+```
+
+In this example, the value is `*29*`.
+
+Your puzzle answer was `54277`.
+";
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        assert_eq!(
+            extract(PUZZLE),
+            vec![
+                Example {
+                    part: Part::One,
+                    input: "1abc2\npqr3stu8vwx\n".into(),
+                    answer: Some("50".into()),
+                },
+                Example {
+                    part: Part::Two,
+                    input: "two1nine\n".into(),
+                    answer: Some("29".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reuses_example_of_part_one() {
+        let puzzle = "--- Day 6 ---\n\nFor example:\n\n```\nTime: 7\n```\n\nThis produces `*288*`.\n\n--- Part Two ---\n\nNow it is `*71503*`.\n";
+        let examples = extract(puzzle);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].input, "Time: 7\n");
+        assert_eq!(examples[1].answer.as_deref(), Some("71503"));
+    }
+
+    #[test]
+    fn extracts_multiple_examples_of_a_part() {
+        let puzzle = "--- Day 8 ---\n\nFor example:\n\n```\nRL\n```\n\nThis takes `*2*` steps.\n\nFor example, here is one that takes `*6*` steps:\n\n```\nLLR\n```\n\n--- Part Two ---\n\nNow it is `*4*`.\n";
+        let examples = extract(puzzle);
+        assert_eq!(examples.len(), 3);
+        assert_eq!(examples[0].input, "RL\n");
+        assert_eq!(examples[0].answer.as_deref(), Some("2"));
+        assert_eq!(examples[1].input, "LLR\n");
+        assert_eq!(examples[1].answer.as_deref(), Some("6"));
+        assert_eq!(examples[2].input, "LLR\n");
+        assert_eq!(file_numbers(&examples), vec![Some(1), Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn numbers_distinct_inputs() {
+        assert_eq!(file_numbers(&extract(PUZZLE)), vec![Some(1), Some(2)]);
+
+        let puzzle = "--- Day 6 ---\n\nFor example:\n\n```\nTime: 7\n```\n\n--- Part Two ---\n\nNow it is `*71503*`.\n";
+        assert_eq!(file_numbers(&extract(puzzle)), vec![None, None]);
+    }
+
+    #[test]
+    fn extracts_html_examples() {
+        let puzzle = "<article><p>For example:</p>\n<pre><code>a &lt; b\n<em>c</em>\n</code></pre>\n<p>The answer is <code><em>4</em></code>.</p></article>";
+        assert_eq!(
+            extract(puzzle),
+            vec![Example {
+                part: Part::One,
+                input: "a < b\nc\n".into(),
+                answer: Some("4".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_blocks_referring_to_an_example() {
+        let puzzle = "--- Day 2 ---\n\nFor example:\n\n```\nexample\n```\n\nIn the example above:\n\n```\nnot an example\n```\n\nThere are *`7`* of them.\n\n--- Part Two ---\n\nAgain considering the above example:\n\n```\nnot an example\n```\n";
+        let examples = extract(puzzle);
+        assert_eq!(examples[0].answer.as_deref(), Some("7"));
+        assert_eq!(examples[1].input, "example\n");
+    }
+
+    #[test]
+    fn parses_example_file_names() {
+        let (year, day) = (year!(2023), day!(8));
+        assert_eq!(parse_example_name("2023_08", year, day), Some((None, None)));
+        assert_eq!(
+            parse_example_name("2023_08-2", year, day),
+            Some((Some(2), None))
+        );
+        assert_eq!(
+            parse_example_name("2023_08_part2", year, day),
+            Some((None, Some(Part::Two)))
+        );
+        assert_eq!(parse_example_name("2023_08-x", year, day), None);
+        assert_eq!(parse_example_name("2023_09", year, day), None);
+    }
+}
@@ -0,0 +1,465 @@
+/// Module that exports benchmark timings as JSON, CSV or a standalone HTML report.
+/// The HTML report draws its charts as inline SVG, so it can be shared as a single file.
+use std::{
+    fmt::{Display, Write},
+    str::FromStr,
+    time::Duration,
+};
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::template::{readme_benchmarks::Timings, report::PartReport};
+use crate::{Day, Part, Year};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Html,
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Json => f.write_str("json"),
+            ExportFormat::Csv => f.write_str("csv"),
+            ExportFormat::Html => f.write_str("html"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExportFormatError(String);
+
+impl Display for ExportFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown export format `{}`, expecting `json`, `csv` or `html`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ExportFormatError {}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(ExportFormatError(s.into())),
+        }
+    }
+}
+
+/// The statistics of one part, or of the parse step if `part` is [`None`].
+#[derive(Serialize)]
+struct PartStats {
+    part: Option<Part>,
+    mean_nanos: u64,
+    median_nanos: u64,
+    min_nanos: u64,
+    max_nanos: u64,
+    p95_nanos: u64,
+    std_dev_nanos: u64,
+    samples: u64,
+    outliers: u64,
+}
+
+impl From<&PartReport> for PartStats {
+    fn from(r: &PartReport) -> Self {
+        Self {
+            part: r.part,
+            mean_nanos: r.mean_nanos,
+            median_nanos: r.median_nanos,
+            min_nanos: r.min_nanos,
+            max_nanos: r.max_nanos,
+            p95_nanos: r.p95_nanos,
+            std_dev_nanos: r.std_dev_nanos,
+            samples: r.samples,
+            outliers: r.outliers,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DayStats {
+    day: Day,
+    total_nanos: u64,
+    parts: Vec<PartStats>,
+}
+
+#[derive(Serialize)]
+struct YearStats {
+    year: Year,
+    total_nanos: u64,
+    days: Vec<DayStats>,
+}
+
+#[derive(Serialize)]
+struct Export {
+    total_nanos: u64,
+    years: Vec<YearStats>,
+}
+
+/// Renders timings in the given format.
+pub fn render(timings: &[Timings], format: ExportFormat) -> serde_json::Result<String> {
+    match format {
+        ExportFormat::Json => to_json(timings),
+        ExportFormat::Csv => Ok(to_csv(timings)),
+        ExportFormat::Html => Ok(to_html(timings)),
+    }
+}
+
+/// Sorts timings by year and day and groups them by year.
+fn by_year(timings: &[Timings]) -> Vec<(Year, Vec<&Timings>)> {
+    timings
+        .iter()
+        .sorted_by_key(|t| (t.year, t.day))
+        .group_by(|t| t.year)
+        .into_iter()
+        .map(|(year, days)| (year, days.collect()))
+        .collect()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn total_nanos<'a>(timings: impl IntoIterator<Item = &'a Timings>) -> u64 {
+    timings.into_iter().map(|t| t.total_nanos).sum::<f64>() as u64
+}
+
+/// Returns the timings of every year, day and part as pretty-printed JSON.
+pub fn to_json(timings: &[Timings]) -> serde_json::Result<String> {
+    let years = by_year(timings)
+        .into_iter()
+        .map(|(year, days)| YearStats {
+            year,
+            total_nanos: total_nanos(days.iter().copied()),
+            days: days
+                .iter()
+                .map(|t| DayStats {
+                    day: t.day,
+                    total_nanos: total_nanos([*t]),
+                    parts: t.reports.iter().map(PartStats::from).collect(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&Export {
+        total_nanos: total_nanos(timings),
+        years,
+    })
+}
+
+/// Returns one CSV row per part, the parse step having the part `parse`.
+#[must_use]
+pub fn to_csv(timings: &[Timings]) -> String {
+    let mut csv = String::from(
+        "year,day,part,mean_nanos,median_nanos,min_nanos,max_nanos,p95_nanos,std_dev_nanos,samples,outliers\n",
+    );
+
+    for (year, days) in by_year(timings) {
+        for t in days {
+            for r in &t.reports {
+                let _ = writeln!(
+                    csv,
+                    "{year},{},{},{},{},{},{},{},{},{},{}",
+                    t.day.into_inner(),
+                    r.part.map_or_else(|| "parse".into(), |p| p.to_string()),
+                    r.mean_nanos,
+                    r.median_nanos,
+                    r.min_nanos,
+                    r.max_nanos,
+                    r.p95_nanos,
+                    r.std_dev_nanos,
+                    r.samples,
+                    r.outliers
+                );
+            }
+        }
+    }
+
+    csv
+}
+
+const STYLE: &str = "\
+body { font-family: sans-serif; max-width: 800px; margin: 2em auto; color: #222; }
+table { border-collapse: collapse; margin: 1em 0 2em; font-size: 0.9em; }
+th, td { padding: 0.2em 0.6em; text-align: right; border-bottom: 1px solid #ddd; }
+svg text { font-size: 11px; fill: #444; }
+svg .label { text-anchor: end; }
+svg .axis { stroke: #ddd; }
+.part-1 { fill: #9ab8d9; }
+.part-2 { fill: #f3c33c; }
+.total { fill: #7bbf7b; }
+";
+
+/// Width of the labels left of the bars.
+const LABEL_WIDTH: f64 = 70.0;
+/// Width of the longest bar, leaving room for its value.
+const BAR_WIDTH: f64 = 560.0;
+const CHART_WIDTH: f64 = 720.0;
+const ROW_HEIGHT: f64 = 24.0;
+const AXIS_HEIGHT: f64 = 20.0;
+
+/// Returns a standalone HTML page with a log-scale overview of every day and a chart and a table
+/// of statistics per year.
+#[must_use]
+pub fn to_html(timings: &[Timings]) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code benchmarks</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n\
+         <h1>Advent of Code benchmarks</h1>\n<p>Total: <strong>{}</strong></p>\n\
+         <h2>Overview</h2>\n{}\n",
+        format_nanos(total_nanos(timings)),
+        overview_chart(timings)
+    );
+
+    for (year, days) in by_year(timings) {
+        let _ = write!(
+            html,
+            "<h2>{year}</h2>\n<p>Total: <strong>{}</strong></p>\n{}\n{}",
+            format_nanos(total_nanos(days.iter().copied())),
+            year_chart(&days),
+            stats_table(&days)
+        );
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_f64(nanos: u64) -> f64 {
+    nanos as f64
+}
+
+fn svg_start(rows: usize, has_axis: bool) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let height = rows as f64 * ROW_HEIGHT + if has_axis { AXIS_HEIGHT } else { 0.0 };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height}\" \
+         viewBox=\"0 0 {CHART_WIDTH} {height}\">\n"
+    )
+}
+
+/// Bars of the median time of both parts of every day, on a linear scale.
+fn year_chart(days: &[&Timings]) -> String {
+    let median = |t: &Timings, part: Part| {
+        t.reports
+            .iter()
+            .find(|r| r.part == Some(part))
+            .map(|r| r.median_nanos)
+    };
+    let max = days
+        .iter()
+        .flat_map(|t| Part::ALL.map(|part| median(t, part)))
+        .flatten()
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut svg = svg_start(days.len(), false);
+
+    for (i, t) in days.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = i as f64 * ROW_HEIGHT;
+        let _ = writeln!(
+            svg,
+            "<text class=\"label\" x=\"{}\" y=\"{}\">Day {}</text>",
+            LABEL_WIDTH - 6.0,
+            y + 15.0,
+            t.day.into_inner()
+        );
+
+        for (j, part) in Part::ALL.into_iter().enumerate() {
+            let Some(nanos) = median(t, part) else {
+                continue;
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = y + 2.0 + j as f64 * 10.0;
+            let width = to_f64(nanos) / to_f64(max) * BAR_WIDTH;
+            let value = format_nanos(nanos);
+            let _ = writeln!(
+                svg,
+                "<rect class=\"part-{part}\" x=\"{LABEL_WIDTH}\" y=\"{bar_y}\" width=\"{width:.1}\" \
+                 height=\"9\"><title>Part {part}: {value}</title></rect>\
+                 <text x=\"{:.1}\" y=\"{}\">{value}</text>",
+                LABEL_WIDTH + width + 4.0,
+                bar_y + 8.0
+            );
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Bars of the total time of every day of every year, on a logarithmic scale.
+fn overview_chart(timings: &[Timings]) -> String {
+    let days: Vec<&Timings> = by_year(timings)
+        .into_iter()
+        .flat_map(|(_, days)| days)
+        .filter(|t| t.total_nanos >= 1.0)
+        .collect();
+
+    let (min, max) = days
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold((f64::MAX, 1.0_f64), |(min, max), n| {
+            (min.min(n), max.max(n))
+        });
+    let low = min.min(max).log10().floor();
+    let high = max.log10().ceil().max(low + 1.0);
+    let x = |nanos: f64| LABEL_WIDTH + (nanos.log10() - low) / (high - low) * BAR_WIDTH;
+
+    let mut svg = svg_start(days.len(), true);
+
+    #[allow(clippy::cast_possible_truncation)]
+    for exponent in low as i32..=high as i32 {
+        let nanos = 10_f64.powi(exponent);
+        let line_x = x(nanos);
+        #[allow(clippy::cast_sign_loss)]
+        let label = format_nanos(nanos as u64);
+        let _ = writeln!(
+            svg,
+            "<line class=\"axis\" x1=\"{line_x:.1}\" y1=\"{AXIS_HEIGHT}\" x2=\"{line_x:.1}\" \
+             y2=\"100%\"/><text x=\"{line_x:.1}\" y=\"12\" text-anchor=\"middle\">{label}</text>"
+        );
+    }
+
+    for (i, t) in days.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = AXIS_HEIGHT + i as f64 * ROW_HEIGHT;
+        let width = x(t.total_nanos) - LABEL_WIDTH;
+        let value = format_nanos(total_nanos([*t]));
+        let _ = writeln!(
+            svg,
+            "<text class=\"label\" x=\"{}\" y=\"{}\">{}/{}</text>\
+             <rect class=\"total\" x=\"{LABEL_WIDTH}\" y=\"{}\" width=\"{width:.1}\" height=\"16\">\
+             <title>{value}</title></rect>",
+            LABEL_WIDTH - 6.0,
+            y + 15.0,
+            t.year,
+            t.day,
+            y + 3.0
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn stats_table(days: &[&Timings]) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Day</th><th>Step</th><th>Median</th><th>Mean</th><th>Std dev</th>\
+         <th>Min</th><th>Max</th><th>p95</th><th>Samples</th><th>Outliers</th></tr>\n",
+    );
+
+    for t in days {
+        for r in &t.reports {
+            let _ = writeln!(
+                table,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td></tr>",
+                t.day.into_inner(),
+                r.part
+                    .map_or_else(|| "Parse".into(), |p| format!("Part {p}")),
+                format_nanos(r.median_nanos),
+                format_nanos(r.mean_nanos),
+                format_nanos(r.std_dev_nanos),
+                format_nanos(r.min_nanos),
+                format_nanos(r.max_nanos),
+                format_nanos(r.p95_nanos),
+                r.samples,
+                r.outliers
+            );
+        }
+    }
+
+    table.push_str("</table>\n");
+    table
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{to_csv, to_html, to_json, ExportFormat};
+    use crate::template::{readme_benchmarks::Timings, report::PartReport};
+    use crate::{day, year, Day, Part, Year};
+
+    fn timings(year: Year, day: Day, medians: [u64; 2]) -> Timings {
+        Timings {
+            year,
+            day,
+            part_1: None,
+            part_2: None,
+            #[allow(clippy::cast_precision_loss)]
+            total_nanos: (medians[0] + medians[1]) as f64,
+            reports: vec![
+                PartReport::mock(year, day, Some(Part::One), None, medians[0]),
+                PartReport::mock(year, day, Some(Part::Two), None, medians[1]),
+            ],
+        }
+    }
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![
+            timings(year!(2023), day!(2), [2_000, 3_000]),
+            timings(year!(2021), day!(1), [10, 20]),
+            timings(year!(2023), day!(1), [1_000_000, 5_000]),
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_json_by_year() {
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(&get_mock_timings()).unwrap()).unwrap();
+
+        assert_eq!(json["total_nanos"], 1_010_030);
+        assert_eq!(json["years"][0]["year"], 2021);
+        assert_eq!(json["years"][1]["total_nanos"], 1_010_000);
+        assert_eq!(json["years"][1]["days"][0]["day"], 1);
+        assert_eq!(json["years"][1]["days"][0]["parts"][1]["part"], 2);
+        assert_eq!(json["years"][1]["days"][0]["parts"][1]["p95_nanos"], 5_004);
+    }
+
+    #[test]
+    fn exports_csv_rows() {
+        let csv = to_csv(&get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[0].starts_with("year,day,part,mean_nanos,median_nanos"));
+        assert_eq!(lines[1], "2021,1,1,11,10,9,15,14,0,100,3");
+        assert_eq!(lines[6], "2023,2,2,3001,3000,2999,3005,3004,30,100,3");
+    }
+
+    #[test]
+    fn exports_html_with_charts() {
+        let html = to_html(&get_mock_timings());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert_eq!(html.matches("<h2>2023</h2>").count(), 1);
+        // the overview has one bar per day, the year charts one per part.
+        assert_eq!(html.matches("class=\"total\"").count(), 3);
+        assert_eq!(html.matches("class=\"part-2\"").count(), 3);
+        assert!(html.contains("<title>Part 1: 1.0ms</title>"));
+    }
+}
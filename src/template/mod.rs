@@ -1,28 +1,131 @@
-use crate::{Day, Year};
-use std::{env, fs};
+use crate::{Day, Part, PuzzleId, Year};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
+pub mod answers;
+pub mod aoc;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod benchmark_history;
+pub mod clock;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod export;
+pub mod ocr;
+pub mod options;
+pub mod progress;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    read_data_file(folder, &format!("{year}_{day}.txt"))
+}
+
+/// Helper function that reads the file of a single part (e.g. `2023_08_part2.txt`) to a string.
+/// Falls back to the file shared by both parts if there is no file for `part`.
+#[must_use]
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: Part) -> String {
+    let name = format!("{year}_{day}_part{part}.txt");
+    if data_file_path(folder, &name).exists() {
+        read_data_file(folder, &name)
+    } else {
+        read_file(folder, year, day)
+    }
+}
+
+/// Helper function that reads a numbered file (e.g. `2023_08-2.txt`) to a string.
+#[must_use]
+pub fn read_file_numbered(folder: &str, year: Year, day: Day, number: u8) -> String {
+    read_data_file(folder, &format!("{year}_{day}-{number}.txt"))
+}
+
+fn data_file_path(folder: &str, name: &str) -> PathBuf {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{year}_{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    cwd.join(config::get().data_path(folder)).join(name)
+}
+
+fn read_data_file(folder: &str, name: &str) -> String {
+    let f = fs::read_to_string(data_file_path(folder, name));
     f.expect("could not open input file")
 }
 
+/// Returns the path of the solution binary for a given year and day.
+#[must_use]
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{year}_{day}.rs")
+}
+
+/// Parses the name of a solution binary (e.g. `2023_05`) into its year and day.
+#[must_use]
+pub fn parse_bin_name(name: &str) -> Option<(Year, Day)> {
+    let (year, day) = name.split_once('_')?;
+    if year.len() != 4 || day.len() != 2 {
+        return None;
+    }
+    let id = PuzzleId::new(year.parse().ok()?, day.parse().ok()?)?;
+    Some(id.into())
+}
+
+/// Lists every scaffolded solution in `./src/bin`, sorted by year and day.
+#[must_use]
+pub fn scaffolded_solutions() -> Vec<(Year, Day)> {
+    let Ok(entries) = fs::read_dir(Path::new("./src/bin")) else {
+        return vec![];
+    };
+
+    let mut solutions: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            parse_bin_name(path.file_stem()?.to_str()?)
+        })
+        .collect();
+
+    solutions.sort_unstable();
+    solutions
+}
+
+/// Returns the default year: the `AOC_YEAR` environment variable if set, or else the
+/// `default_year` of the config.
+#[must_use]
+pub fn get_year() -> Option<Year> {
+    match env::var("AOC_YEAR") {
+        Ok(year) => year.parse().ok(),
+        Err(_) => config::get().default_year,
+    }
+}
+
 /// Creates the constants `YEAR`, `DAY` and sets up the input and runner for each part.
+///
+/// Solutions either implement `part_one` and `part_two` on the raw input, or pass the name of a
+/// parse function as third argument. In that case, the parsed input is shared by both parts,
+/// which take a reference to it, and parsing is timed separately.
+///
+/// ```ignore
+/// advent_of_code::solution!(2023, 9, parse_input);
+///
+/// fn parse_input(input: &str) -> Histories { /* ... */ }
+/// pub fn part_one(histories: &Histories) -> Option<i64> { /* ... */ }
+/// pub fn part_two(histories: &Histories) -> Option<i64> { /* ... */ }
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($year:expr, $day:expr) => {
@@ -30,11 +133,93 @@ macro_rules! solution {
         const YEAR: advent_of_code::Year = advent_of_code::year!($year);
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// This day's entry in the solution registry.
+        #[allow(dead_code)]
+        pub static SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                solve: |input, options| {
+                    use advent_of_code::template::runner::*;
+                    run_part(
+                        part_one,
+                        input,
+                        YEAR,
+                        DAY,
+                        advent_of_code::Part::One,
+                        options,
+                    );
+                    run_part(
+                        part_two,
+                        input,
+                        YEAR,
+                        DAY,
+                        advent_of_code::Part::Two,
+                        options,
+                    );
+                },
+            };
+
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", YEAR, DAY);
-            run_part(part_one, &input, YEAR, DAY, 1);
-            run_part(part_two, &input, YEAR, DAY, 2);
+            advent_of_code::template::runner::run_binary(&SOLUTION);
         }
     };
+    ($year:expr, $day:expr, $parse:path) => {
+        /// The current day.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        /// This day's entry in the solution registry.
+        #[allow(dead_code)]
+        pub static SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                solve: |input, options| {
+                    use advent_of_code::template::runner::*;
+                    let parsed = run_parse($parse, input, YEAR, DAY, options);
+                    run_part(
+                        part_one,
+                        &parsed,
+                        YEAR,
+                        DAY,
+                        advent_of_code::Part::One,
+                        options,
+                    );
+                    run_part(
+                        part_two,
+                        &parsed,
+                        YEAR,
+                        DAY,
+                        advent_of_code::Part::Two,
+                        options,
+                    );
+                },
+            };
+
+        fn main() {
+            advent_of_code::template::runner::run_binary(&SOLUTION);
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_bin_name;
+    use crate::{day, year};
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(parse_bin_name("2023_05"), Some((year!(2023), day!(5))));
+        assert_eq!(parse_bin_name("2021_25"), Some((year!(2021), day!(25))));
+    }
+
+    #[test]
+    fn rejects_invalid_bin_names() {
+        assert_eq!(parse_bin_name("05"), None);
+        assert_eq!(parse_bin_name("2023_5"), None);
+        assert_eq!(parse_bin_name("2023_26"), None);
+        assert_eq!(parse_bin_name("1999_01"), None);
+        assert_eq!(parse_bin_name("y_2023_d7_refactor_this"), None);
+    }
 }
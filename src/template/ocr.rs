@@ -0,0 +1,242 @@
+//! Module that reads the capital letters that some puzzles draw on a screen of `#` and `.`.
+//! Puzzles use one of two fonts: letters 6 pixels high and mostly 4 wide, or 10 high and 6 wide.
+
+/// A font of letters drawn in ASCII art.
+struct Font {
+    height: usize,
+    /// The rows of every letter, joined by newlines. Letters start and end with a lit column.
+    glyphs: &'static [(char, &'static str)],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    glyphs: &[
+        ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+        ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+        ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+        ('E', "####\n#...\n###.\n#...\n#...\n####"),
+        ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+        ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+        ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+        ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+        ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+        ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+        ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+        ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+        ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+        ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+        ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+        ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+        ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+    ],
+};
+
+const LARGE: Font = Font {
+    height: 10,
+    glyphs: &[
+        (
+            'A',
+            "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'B',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+        ),
+        (
+            'C',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+        ),
+        (
+            'E',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'F',
+            "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'G',
+            ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+        ),
+        (
+            'H',
+            "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+        ),
+        (
+            'J',
+            "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+        ),
+        (
+            'K',
+            "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+        ),
+        (
+            'L',
+            "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+        ),
+        (
+            'N',
+            "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+        ),
+        (
+            'P',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+        ),
+        (
+            'R',
+            "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+        ),
+        (
+            'X',
+            "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+        ),
+        (
+            'Z',
+            "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+        ),
+    ],
+};
+
+impl Font {
+    /// Reads the letters of a screen, given as columns of pixels, or returns [`None`] if a letter
+    /// is unknown. Letters are usually separated by dark columns, but wide letters may touch the
+    /// next one, so every letter that matches is tried until the rest of the screen reads too.
+    fn read(&self, columns: &[Vec<bool>]) -> Option<String> {
+        let Some(start) = columns.iter().position(|col| col.contains(&true)) else {
+            return Some(String::new());
+        };
+        let columns = &columns[start..];
+
+        self.glyphs.iter().find_map(|(letter, glyph)| {
+            let width = glyph.find('\n').unwrap_or(glyph.len());
+            if width > columns.len() || !draws(glyph, &columns[..width]) {
+                return None;
+            }
+            let rest = self.read(&columns[width..])?;
+            Some(format!("{letter}{rest}"))
+        })
+    }
+}
+
+/// Whether `columns` show exactly the pixels of `glyph`.
+fn draws(glyph: &str, columns: &[Vec<bool>]) -> bool {
+    glyph.lines().enumerate().all(|(row, line)| {
+        line.bytes()
+            .zip(columns)
+            .all(|(pixel, col)| col[row] == (pixel == b'#'))
+    })
+}
+
+/// Reads the letters drawn by `art`, with `#` for lit and `.` or spaces for dark pixels.
+/// Returns [`None`] if `art` is not a screen of known letters in one of the fonts.
+#[must_use]
+pub fn parse(art: &str) -> Option<String> {
+    let rows: Vec<&str> = art
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.is_empty())
+        .collect();
+    let rows = &rows[..rows.iter().rposition(|line| !line.is_empty())? + 1];
+
+    let pixels = rows
+        .iter()
+        .map(|row| {
+            row.chars()
+                .map(|c| match c {
+                    '#' => Some(true),
+                    '.' | ' ' => Some(false),
+                    _ => None,
+                })
+                .collect::<Option<Vec<bool>>>()
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    // shorter rows are dark at their end.
+    let width = pixels.iter().map(Vec::len).max()?;
+    let columns: Vec<Vec<bool>> = (0..width)
+        .map(|col| {
+            pixels
+                .iter()
+                .map(|row| row.get(col).copied().unwrap_or(false))
+                .collect()
+        })
+        .collect();
+
+    [SMALL, LARGE]
+        .iter()
+        .find(|font| font.height == pixels.len())?
+        .read(&columns)
+        .filter(|letters| !letters.is_empty())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn reads_small_letters() {
+        let art = [
+            "###..#..#.####..##.",
+            "#..#.#..#.#....#..#",
+            "#..#.####.###..#...",
+            "###..#..#.#....#.##",
+            "#....#..#.#....#..#",
+            "#....#..#.####..###",
+        ]
+        .join("\n");
+
+        assert_eq!(parse(&art), Some("PHEG".into()));
+        assert_eq!(
+            parse(&format!("\n{}\n", art.replace('.', " "))),
+            Some("PHEG".into())
+        );
+    }
+
+    #[test]
+    fn reads_letters_of_any_width() {
+        // the Y fills its cell and touches the Z, the I is only 3 pixels wide.
+        let art = [
+            "#...#####..###.",
+            "#...#...#...#..",
+            ".#.#...#....#..",
+            "..#...#.....#..",
+            "..#..#......#..",
+            "..#..####..###.",
+        ]
+        .join("\n");
+
+        assert_eq!(parse(&art), Some("YZI".into()));
+        assert_eq!(
+            parse(&format!("..{}", art.replace('\n', "\n.."))),
+            Some("YZI".into())
+        );
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let art = [
+            "#....#..######",
+            "#....#..#.....",
+            ".#..#...#.....",
+            ".#..#...#.....",
+            "..##....#####.",
+            "..##....#.....",
+            ".#..#...#.....",
+            ".#..#...#.....",
+            "#....#..#.....",
+            "#....#..######",
+        ]
+        .join("\n");
+
+        assert_eq!(parse(&art), Some("XE".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_art() {
+        assert_eq!(parse("1\n2"), None);
+        assert_eq!(parse("####\n####\n####\n####\n####\n####"), None);
+        assert_eq!(parse("#..#\n#..#\n####\n#..#\n#..#\n#.x#"), None);
+        assert_eq!(parse(""), None);
+    }
+}
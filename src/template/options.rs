@@ -0,0 +1,395 @@
+/// Module that parses the command line of solution binaries into [`RunOptions`].
+/// The runner receives the options explicitly instead of inspecting the arguments itself.
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::template::{config, examples, read_file, read_file_numbered, read_file_part};
+use crate::{Day, Part, Year};
+
+pub const HELP: &str = "\
+Runs both parts of a solution against the puzzle input.
+
+Usage: cargo run --bin <year>_<day> -- [OPTIONS]
+
+Options:
+  --time             Benchmark every part
+  --submit <part>    Submit the answer of part 1 or 2
+  --part <part>      Only run part 1 or 2
+  --input <path>     Read the input from a file instead of the puzzle input, or from stdin for `-`
+  --example [n]      Read the input from the example file, or the numbered example file n
+  --format <format>  Print results as `text` (default) or as `json` lines
+  --timeout <secs>   Abandon a part that runs longer than this
+  -h, --help         Print this help
+";
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The personal puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    /// The example file in `data/examples`, or one of the numbered example files.
+    Example(Option<u8>),
+}
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One [`PartReport`](crate::template::report::PartReport) per line, as JSON.
+    Json,
+}
+
+impl InputSource {
+    /// Returns the input source of an `--input` argument, where `-` stands for stdin.
+    fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => f.write_str("text"),
+            Format::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = OptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(OptionsError::Invalid(format!(
+                "unknown format `{s}`, expecting `text` or `json`"
+            ))),
+        }
+    }
+}
+
+/// The options a solution runs with.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RunOptions {
+    pub time: bool,
+    pub submit: Option<Part>,
+    /// Only run this part, or both if [`None`].
+    pub part: Option<Part>,
+    pub input: InputSource,
+    pub format: Format,
+    /// Seconds a part may run, overriding `run.timeout_secs` of the config.
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum OptionsError {
+    Args(pico_args::Error),
+    Invalid(String),
+}
+
+impl Display for OptionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionsError::Args(e) => write!(f, "{e}"),
+            OptionsError::Invalid(s) => f.write_str(s),
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+impl From<pico_args::Error> for OptionsError {
+    fn from(e: pico_args::Error) -> Self {
+        OptionsError::Args(e)
+    }
+}
+
+impl RunOptions {
+    /// Parses the arguments of a solution binary, without the program name.
+    /// Returns [`None`] if help was requested.
+    pub fn parse(args: Vec<OsString>) -> Result<Option<Self>, OptionsError> {
+        let mut args = pico_args::Arguments::from_vec(args);
+
+        if args.contains(["-h", "--help"]) {
+            return Ok(None);
+        }
+
+        let mut options = Self::from_flags(&mut args)?;
+        options.take_example_number(&mut args)?;
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(OptionsError::Invalid(format!(
+                "unexpected argument(s): {remaining:?}"
+            )));
+        }
+
+        options.validate()?;
+        Ok(Some(options))
+    }
+
+    /// Takes the flags of the options out of `args`, leaving free arguments for the caller.
+    /// The number of `--example [n]` is a free argument, see [`RunOptions::take_example_number`].
+    pub fn from_flags(args: &mut pico_args::Arguments) -> Result<Self, OptionsError> {
+        let time = args.contains("--time");
+        let submit = args.opt_value_from_str("--submit")?;
+        let part = args.opt_value_from_str("--part")?;
+        let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+        let timeout_secs = args.opt_value_from_str("--timeout")?;
+        let file: Option<String> = args.opt_value_from_str("--input")?;
+        let example = args.contains("--example");
+
+        let input = match (file, example) {
+            (Some(_), true) => {
+                return Err(OptionsError::Invalid(
+                    "--input and --example cannot be used together".into(),
+                ))
+            }
+            (Some(arg), false) => InputSource::from_arg(&arg),
+            (None, true) => InputSource::Example(None),
+            (None, false) => InputSource::Puzzle,
+        };
+
+        Ok(Self {
+            time,
+            submit,
+            part,
+            input,
+            format,
+            timeout_secs,
+        })
+    }
+
+    /// Takes the optional number of `--example [n]` from the next free argument.
+    pub fn take_example_number(
+        &mut self,
+        args: &mut pico_args::Arguments,
+    ) -> Result<(), OptionsError> {
+        if self.input == InputSource::Example(None) {
+            self.input = InputSource::Example(args.opt_free_from_str()?);
+        }
+        Ok(())
+    }
+
+    /// Checks that the options do not contradict each other.
+    pub fn validate(&self) -> Result<(), OptionsError> {
+        if self.submit.is_some() && !self.is_puzzle_input() {
+            return Err(OptionsError::Invalid(
+                "--submit can only be used with the puzzle input".into(),
+            ));
+        }
+
+        if self.timeout_secs == Some(0) {
+            return Err(OptionsError::Invalid("--timeout must be positive".into()));
+        }
+
+        if let (Some(submit), Some(part)) = (self.submit, self.part) {
+            if submit != part {
+                return Err(OptionsError::Invalid(format!(
+                    "cannot submit part {submit} while only running part {part}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts the options back into the arguments of a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".into());
+        }
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+        if let Some(part) = self.part {
+            args.extend(["--part".into(), part.to_string()]);
+        }
+        match &self.input {
+            InputSource::Puzzle => {}
+            InputSource::File(path) => {
+                args.extend(["--input".into(), path.to_string_lossy().into_owned()]);
+            }
+            InputSource::Stdin => args.extend(["--input".into(), "-".into()]),
+            InputSource::Example(number) => {
+                args.push("--example".into());
+                args.extend(number.map(|n| n.to_string()));
+            }
+        }
+        if self.format != Format::Text {
+            args.extend(["--format".into(), self.format.to_string()]);
+        }
+        if let Some(secs) = self.timeout_secs {
+            args.extend(["--timeout".into(), secs.to_string()]);
+        }
+
+        args
+    }
+
+    /// Parses the arguments of the running solution binary.
+    /// Prints the help or the error and exits if the arguments do not describe a run.
+    #[must_use]
+    pub fn from_env() -> Self {
+        match Self::parse(env::args_os().skip(1).collect()) {
+            Ok(Some(options)) => options,
+            Ok(None) => {
+                print!("{HELP}");
+                process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("Error: {e}\n\n{HELP}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// How long a part may run, from `--timeout` or the config. [`None`] lets parts run forever.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs
+            .or(config::get().run.timeout_secs)
+            .map(Duration::from_secs)
+    }
+
+    /// Whether `part` should run.
+    #[must_use]
+    pub fn runs(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Whether the solution runs against the personal puzzle input, whose answers can be checked,
+    /// submitted and benchmarked.
+    #[must_use]
+    pub fn is_puzzle_input(&self) -> bool {
+        self.input == InputSource::Puzzle
+    }
+
+    /// Splits these options into one run per input. Runs of both parts against the example are
+    /// split by part if the day has per-part example files, so that each part reads its own file.
+    #[must_use]
+    pub fn split_by_input(&self, year: Year, day: Day) -> Vec<RunOptions> {
+        let has_part_files = || examples::list(year, day).iter().any(|f| f.part.is_some());
+
+        if self.input == InputSource::Example(None) && self.part.is_none() && has_part_files() {
+            Part::ALL
+                .map(|part| RunOptions {
+                    part: Some(part),
+                    ..self.clone()
+                })
+                .to_vec()
+        } else {
+            vec![self.clone()]
+        }
+    }
+
+    /// Reads the input selected by these options.
+    ///
+    /// # Panics
+    /// Panics if the input file cannot be read.
+    #[must_use]
+    pub fn read_input(&self, year: Year, day: Day) -> String {
+        match &self.input {
+            InputSource::Puzzle => read_file("inputs", year, day),
+            InputSource::File(path) => fs::read_to_string(path)
+                .unwrap_or_else(|e| panic!("could not open input file {}: {e}", path.display())),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .unwrap_or_else(|e| panic!("could not read input from stdin: {e}"));
+                input
+            }
+            InputSource::Example(Some(number)) => {
+                read_file_numbered("examples", year, day, *number)
+            }
+            InputSource::Example(None) => match self.part {
+                Some(part) => read_file_part("examples", year, day, part),
+                None => read_file("examples", year, day),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{ffi::OsString, path::PathBuf};
+
+    use super::{Format, InputSource, RunOptions};
+    use crate::Part;
+
+    fn parse(args: &[&str]) -> Result<Option<RunOptions>, super::OptionsError> {
+        RunOptions::parse(args.iter().map(OsString::from).collect())
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(parse(&[]).unwrap(), Some(RunOptions::default()));
+        assert_eq!(parse(&["--help"]).unwrap(), None);
+
+        let options = parse(&["--time", "--submit", "2", "--format", "json"])
+            .unwrap()
+            .unwrap();
+        assert!(options.time);
+        assert_eq!(options.submit, Some(Part::Two));
+        assert_eq!(options.format, Format::Json);
+
+        let options = parse(&["--example", "2", "--part", "1"]).unwrap().unwrap();
+        assert_eq!(options.input, InputSource::Example(Some(2)));
+        assert!(options.runs(Part::One));
+        assert!(!options.runs(Part::Two));
+
+        let options = parse(&["--input", "stress.txt"]).unwrap().unwrap();
+        assert_eq!(
+            options.input,
+            InputSource::File(PathBuf::from("stress.txt"))
+        );
+        assert!(!options.is_puzzle_input());
+
+        let options = parse(&["--input", "-"]).unwrap().unwrap();
+        assert_eq!(options.input, InputSource::Stdin);
+    }
+
+    #[test]
+    fn round_trips_arguments() {
+        for args in [
+            vec!["--time", "--submit", "1", "--part", "1"],
+            vec!["--input", "-", "--format", "json", "--timeout", "5"],
+            vec!["--example", "3"],
+            vec!["--example"],
+        ] {
+            let options = parse(&args).unwrap().unwrap();
+            assert_eq!(options.to_args(), args);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        assert!(parse(&["--submit", "3"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["--input", "a.txt", "--example"]).is_err());
+        assert!(parse(&["--example", "--submit", "1"]).is_err());
+        assert!(parse(&["--part", "1", "--submit", "2"]).is_err());
+        assert!(parse(&["--timeout", "0"]).is_err());
+    }
+}
@@ -0,0 +1,375 @@
+/// Module that derives the stars of every day from the known answers and the submission log, and
+/// from the private leaderboards of the Advent of Code website if asked to.
+/// The stars replace the tables that the `advent-readme-stars` action used to write to the README.
+use std::{collections::BTreeMap, env, fmt::Display, fs, io};
+
+use serde::{de::IgnoredAny, Deserialize};
+
+use crate::template::{
+    answers,
+    aoc_client::{AocClientError, Verdict},
+    config, parse_bin_name,
+    readme_benchmarks::{self, Markers},
+    submissions,
+};
+use crate::{Day, Part, Year};
+
+/// The markers of the stars tables.
+pub const STARS: Markers = Markers("advent_readme_stars table");
+
+/// The parts of a day that are solved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stars {
+    pub part_one: bool,
+    pub part_two: bool,
+}
+
+impl Stars {
+    #[must_use]
+    pub fn count(self) -> usize {
+        usize::from(self.part_one) + usize::from(self.part_two)
+    }
+
+    fn add(&mut self, part: Part) {
+        match part {
+            Part::One => self.part_one = true,
+            Part::Two => self.part_two = true,
+        }
+    }
+}
+
+/// The stars of every day with at least one star, by year.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    years: BTreeMap<Year, BTreeMap<Day, Stars>>,
+}
+
+/// A private leaderboard, as served by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// The parts each member solved, keyed by day and then by part.
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, IgnoredAny>>,
+}
+
+#[derive(Debug)]
+pub enum ProgressError {
+    Leaderboard(String),
+    Client(AocClientError),
+    IO(io::Error),
+}
+
+impl Display for ProgressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgressError::Leaderboard(s) => write!(f, "invalid leaderboard: {s}"),
+            ProgressError::Client(e) => write!(f, "{e}"),
+            ProgressError::IO(e) => write!(f, "could not read progress: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for ProgressError {
+    fn from(e: io::Error) -> Self {
+        ProgressError::IO(e)
+    }
+}
+
+impl From<AocClientError> for ProgressError {
+    fn from(e: AocClientError) -> Self {
+        ProgressError::Client(e)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, ProgressError> {
+        serde_json::from_str(json).map_err(|e| ProgressError::Leaderboard(e.to_string()))
+    }
+
+    /// Returns the year of the event the leaderboard belongs to.
+    pub fn year(&self) -> Result<Year, ProgressError> {
+        self.event
+            .parse()
+            .map_err(|e| ProgressError::Leaderboard(format!("event {}: {e}", self.event)))
+    }
+
+    /// Returns the member with `user_id`, or the only member if `user_id` is [`None`].
+    pub fn member(&self, user_id: Option<u64>) -> Result<&Member, ProgressError> {
+        match user_id {
+            Some(id) => self
+                .members
+                .values()
+                .find(|m| m.id == id)
+                .ok_or_else(|| ProgressError::Leaderboard(format!("user {id} is not a member"))),
+            None if self.members.len() == 1 => Ok(self.members.values().next().unwrap()),
+            None => Err(ProgressError::Leaderboard(format!(
+                "{} members, set user_id in aoc.toml or AOC_USER_ID to pick one",
+                self.members.len()
+            ))),
+        }
+    }
+}
+
+impl Progress {
+    /// Collects the stars of `year`, or of every year if [`None`], from the known answers and the
+    /// correct submissions in the data directory.
+    pub fn load_local(year: Option<Year>) -> io::Result<Self> {
+        let mut days: Vec<(Year, Day)> = ["answers", "submissions"]
+            .iter()
+            .filter_map(|folder| fs::read_dir(config::get().data_path(folder)).ok())
+            .flatten()
+            .filter_map(|entry| parse_bin_name(entry.ok()?.path().file_stem()?.to_str()?))
+            .filter(|(y, _)| year.is_none_or(|year| *y == year))
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+
+        let mut progress = Self::default();
+
+        for (year, day) in days {
+            let known = answers::load(year, day)?;
+            for part in Part::ALL {
+                if known.get(part).is_some() {
+                    progress.add(year, day, part);
+                }
+            }
+
+            for submission in submissions::load(year, day)? {
+                if submission.verdict == Verdict::Correct {
+                    progress.add(year, day, submission.part);
+                }
+            }
+        }
+
+        Ok(progress)
+    }
+
+    /// Adds the stars of a member of a leaderboard.
+    pub fn add_leaderboard(
+        &mut self,
+        leaderboard: &Leaderboard,
+        user_id: Option<u64>,
+    ) -> Result<(), ProgressError> {
+        let year = leaderboard.year()?;
+
+        for (day, parts) in &leaderboard.member(user_id)?.completion_day_level {
+            let day = day
+                .parse()
+                .map_err(|e| ProgressError::Leaderboard(format!("day {day}: {e}")))?;
+            for part in parts.keys() {
+                let part = part
+                    .parse()
+                    .map_err(|e| ProgressError::Leaderboard(format!("part {part}: {e}")))?;
+                self.add(year, day, part);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn add(&mut self, year: Year, day: Day, part: Part) {
+        self.years
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+            .add(part);
+    }
+
+    /// Returns the years with at least one star.
+    pub fn years(&self) -> impl Iterator<Item = Year> + '_ {
+        self.years.keys().copied()
+    }
+
+    /// Returns the number of stars of a year.
+    #[must_use]
+    pub fn count(&self, year: Year) -> usize {
+        self.years
+            .get(&year)
+            .map_or(0, |days| days.values().map(|s| s.count()).sum())
+    }
+}
+
+/// Returns the id of the user on the website: the `AOC_USER_ID` environment variable if set,
+/// or else the `user_id` of the config.
+#[must_use]
+pub fn get_user_id() -> Option<u64> {
+    match env::var("AOC_USER_ID") {
+        Ok(id) => id.trim().parse().ok(),
+        Err(_) => config::get().user_id,
+    }
+}
+
+/// Returns the years that have a stars table in the README. A missing README has no tables.
+pub fn readme_years() -> Result<Vec<Year>, readme_benchmarks::Error> {
+    let readme = match fs::read(&config::get().readme) {
+        Ok(readme) => String::from_utf8_lossy(&readme).to_string(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(STARS
+        .year_tables(&readme)?
+        .into_iter()
+        .map(|(year, _)| year)
+        .collect())
+}
+
+fn construct_year_table(year: Year, days: &BTreeMap<Day, Stars>) -> String {
+    let mut lines: Vec<String> = vec![
+        format!("### {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |solved: bool| if solved { "⭐" } else { " " };
+    for (day, stars) in days {
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            star(stars.part_one),
+            star(stars.part_two),
+            day = day.into_inner()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn construct_summary(years: &[(Year, usize)]) -> String {
+    let total = years.iter().map(|(_, stars)| stars).sum::<usize>();
+
+    let mut lines: Vec<String> = vec![
+        "## Results".into(),
+        String::new(),
+        "| Year | Stars |".into(),
+        "| :---: | :---: |".into(),
+    ];
+
+    for (year, stars) in years {
+        lines.push(format!("| [{year}](#{year}-results) | {stars} ⭐ |"));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total} ⭐**"));
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress) -> Result<(), readme_benchmarks::Error> {
+    for (year, days) in &progress.years {
+        STARS.write_year_table(s, *year, &construct_year_table(*year, days))?;
+    }
+
+    // the summary counts the tables in the README, including those of years that were not loaded.
+    let mut years: Vec<(Year, usize)> = STARS
+        .year_tables(s)?
+        .into_iter()
+        .map(|(year, range)| (year, s[range].matches('⭐').count()))
+        .collect();
+    years.sort_unstable();
+
+    STARS.write_summary(s, &construct_summary(&years))?;
+    Ok(())
+}
+
+/// Writes the stars tables of every year in `progress` to the README and refreshes the summary.
+pub fn update_readme(progress: &Progress) -> Result<(), readme_benchmarks::Error> {
+    readme_benchmarks::edit_readme(|readme| update_content(readme, progress))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Leaderboard, Progress};
+    use crate::{day, year, Part};
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 3031,
+        "members": {
+            "3031": {
+                "id": 3031,
+                "name": "me",
+                "stars": 3,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701407000, "star_index": 1 },
+                        "2": { "get_star_ts": 1701407300, "star_index": 2 }
+                    },
+                    "3": { "1": { "get_star_ts": 1701580000, "star_index": 5 } }
+                }
+            },
+            "42": { "id": 42, "name": null, "stars": 0, "completion_day_level": {} }
+        }
+    }"#;
+
+    #[test]
+    fn reads_stars_from_leaderboards() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+
+        let mut progress = Progress::default();
+        progress.add_leaderboard(&leaderboard, Some(3031)).unwrap();
+        assert_eq!(progress.count(year!(2023)), 3);
+
+        let mut progress = Progress::default();
+        progress.add(year!(2023), day!(3), Part::Two);
+        progress.add_leaderboard(&leaderboard, Some(3031)).unwrap();
+        assert_eq!(progress.count(year!(2023)), 4);
+
+        assert!(progress.add_leaderboard(&leaderboard, None).is_err());
+        assert!(progress.add_leaderboard(&leaderboard, Some(7)).is_err());
+    }
+
+    #[test]
+    fn writes_stars_tables() {
+        let mut s = "foo\n<!--- advent_readme_stars table --->\nbaz".to_string();
+
+        let mut progress = Progress::default();
+        progress.add(year!(2023), day!(1), Part::One);
+        progress.add(year!(2023), day!(1), Part::Two);
+        progress.add(year!(2023), day!(2), Part::One);
+        progress.add(year!(2021), day!(25), Part::One);
+        update_content(&mut s, &progress).unwrap();
+        update_content(&mut s, &progress).unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## Results",
+            "",
+            "| Year | Stars |",
+            "| :---: | :---: |",
+            "| [2021](#2021-results) | 1 ⭐ |",
+            "| [2023](#2023-results) | 3 ⭐ |",
+            "",
+            "**Total: 4 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "<!--- advent_readme_stars table 2021 --->",
+            "### 2021 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 25](https://adventofcode.com/2021/day/25) | ⭐ |   |",
+            "<!--- advent_readme_stars table 2021 --->",
+            "",
+            "<!--- advent_readme_stars table 2023 --->",
+            "### 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table 2023 --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -1,10 +1,17 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Every year has its own table between a pair of year markers (e.g. `<!--- benchmarking table 2023 --->`),
+/// so that updating one year leaves the others alone. The pair of plain markers holds a summary of
+/// every year table in the README. Tables of years without markers are added after the summary.
+/// Other generated tables, like the stars of `progress`, use the same [`Markers`].
+use std::{collections::BTreeMap, fs, io, ops::Range};
 
-use crate::Day;
+use crate::template::{config, get_path_for_bin, report::PartReport};
+use crate::{Day, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// The markers of the benchmark tables.
+pub const BENCHMARKS: Markers = Markers("benchmarking table");
 
 #[derive(Debug)]
 pub enum Error {
@@ -20,112 +27,264 @@ impl From<std::io::Error> for Error {
 
 #[derive(Clone)]
 pub struct Timings {
+    pub year: Year,
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// The reports the timings were made from, with the statistics of every sample.
+    pub reports: Vec<PartReport>,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
-}
+/// A kind of generated README table, named by the text of its markers: one table per year between
+/// `<!--- {name} {year} --->` markers and a summary between `<!--- {name} --->` markers.
+#[derive(Debug, Clone, Copy)]
+pub struct Markers(pub &'static str);
+
+impl Markers {
+    fn summary(self) -> String {
+        format!("<!--- {} --->", self.0)
+    }
+
+    fn year(self, year: Year) -> String {
+        format!("<!--- {} {year} --->", self.0)
+    }
+
+    /// Finds the tables of every year in the README, in the order they appear.
+    pub fn year_tables(self, readme: &str) -> Result<Vec<(Year, Range<usize>)>, Error> {
+        let prefix = format!("<!--- {} ", self.0);
+
+        let mut tables: Vec<(Year, Range<usize>)> = vec![];
+        for (i, _) in readme.match_indices(&prefix) {
+            let rest = &readme[i + prefix.len()..];
+            let Some(year) = rest
+                .get(..4)
+                .filter(|_| rest[4..].starts_with(" --->"))
+                .and_then(|year| year.parse().ok())
+            else {
+                continue;
+            };
+
+            if tables.iter().any(|(y, _)| *y == year) {
+                continue;
+            }
+            if let Some(range) = locate_table(readme, &self.year(year))? {
+                tables.push((year, range));
+            }
+        }
+
+        Ok(tables)
+    }
+
+    /// Replaces the table of `year` with `content`, adding the table if it has no markers yet.
+    pub fn write_year_table(
+        self,
+        readme: &mut String,
+        year: Year,
+        content: &str,
+    ) -> Result<(), Error> {
+        let marker = self.year(year);
+        let table = format!("{marker}\n{content}\n{marker}");
+
+        match locate_table(readme, &marker)? {
+            Some(range) => readme.replace_range(range, &table),
+            None => {
+                let position = self.insert_position(readme, year)?;
+                readme.insert_str(position, &format!("\n\n{table}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the summary with `content`. Returns `false` if the README has no summary markers.
+    pub fn write_summary(self, readme: &mut String, content: &str) -> Result<bool, Error> {
+        let marker = self.summary();
+        let Some(range) = locate_table(readme, &marker)? else {
+            return Ok(false);
+        };
+
+        readme.replace_range(range, &format!("{marker}\n{content}\n{marker}"));
+        Ok(true)
+    }
+
+    /// Position of the table of a year without markers: before the first table of a later year,
+    /// after the last table of an earlier year, or else after the summary.
+    fn insert_position(self, readme: &str, year: Year) -> Result<usize, Error> {
+        let tables = self.year_tables(readme)?;
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+        if let Some((_, range)) = tables
+            .iter()
+            .filter(|(y, _)| *y > year)
+            .min_by_key(|(_, r)| r.start)
+        {
+            return Ok(range.start);
+        }
+        if let Some((_, range)) = tables
+            .iter()
+            .filter(|(y, _)| *y < year)
+            .max_by_key(|(_, r)| r.end)
+        {
+            return Ok(range.end);
+        }
+
+        locate_table(readme, &self.summary())?
+            .map(|range| range.end)
+            .ok_or_else(|| Error::Parser("Could not find table position.".into()))
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table between a pair of `marker`s. A single marker is an empty table.
+fn locate_table(readme: &str, marker: &str) -> Result<Option<Range<usize>>, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    let (Some(first), Some(last)) = (matches.first(), matches.last()) else {
+        return Ok(None);
+    };
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    Ok(Some(first.0..last.0 + last.1.len()))
+}
 
-    Ok(TablePosition { pos_start, pos_end })
+/// Applies `f` to the README configured in `aoc.toml`.
+pub fn edit_readme(f: impl FnOnce(&mut String) -> Result<(), Error>) -> Result<(), Error> {
+    let path = &config::get().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    f(&mut readme)?;
+    fs::write(path, &readme)?;
+    Ok(())
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_year_table(year: Year, timings: &[Timings]) -> String {
+    let total_millis = timings.iter().map(|t| t.total_nanos).sum::<f64>() / 1_000_000_f64;
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
+        format!("### {year} Benchmarks"),
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
         "| :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
         lines.push(format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            get_path_for_bin(year, timing.day),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Reads the number of days and the total of a year table written by [`construct_year_table`].
+fn parse_year_table(table: &str) -> (usize, f64) {
+    let days = table.lines().filter(|l| l.starts_with("| [Day ")).count();
+    let total_millis = table
+        .lines()
+        .find_map(|l| {
+            l.strip_prefix("**Total: ")?
+                .strip_suffix("ms**")?
+                .parse()
+                .ok()
+        })
+        .unwrap_or_default();
+
+    (days, total_millis)
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+fn construct_summary(years: &[(Year, usize, f64)]) -> String {
+    let total_millis = years.iter().map(|(_, _, millis)| millis).sum::<f64>();
+
+    let mut lines: Vec<String> = vec![
+        "## Benchmarks".into(),
+        String::new(),
+        "| Year | Days | Total |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (year, days, millis) in years {
+        lines.push(format!(
+            "| [{year}](#{year}-benchmarks) | {days} | `{millis:.2}ms` |"
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, timings: Vec<Timings>) -> Result<(), Error> {
+    let mut by_year: BTreeMap<Year, Vec<Timings>> = BTreeMap::new();
+    for timing in timings {
+        by_year.entry(timing.year).or_default().push(timing);
+    }
+
+    for (year, timings) in by_year {
+        BENCHMARKS.write_year_table(s, year, &construct_year_table(year, &timings))?;
+    }
+
+    let mut years: Vec<(Year, usize, f64)> = BENCHMARKS
+        .year_tables(s)?
+        .into_iter()
+        .map(|(year, range)| {
+            let (days, millis) = parse_year_table(&s[range]);
+            (year, days, millis)
+        })
+        .collect();
+    years.sort_by_key(|(year, _, _)| *year);
+
+    BENCHMARKS.write_summary(s, &construct_summary(&years))?;
     Ok(())
 }
 
+/// Writes the tables of the years in `timings` to the README and refreshes the summary.
+pub fn update(timings: Vec<Timings>) -> Result<(), Error> {
+    edit_readme(|readme| update_content(readme, timings))
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{update_content, Timings};
+    use crate::{day, year, Year};
 
-    fn get_mock_timings() -> Vec<Timings> {
+    static MARKER: &str = "<!--- benchmarking table --->";
+
+    fn get_mock_timings(year: Year) -> Vec<Timings> {
         vec![
             Timings {
+                year,
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
+                total_nanos: 3e+7,
+                reports: vec![],
             },
             Timings {
+                year,
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
+                total_nanos: 7e+7,
+                reports: vec![],
             },
             Timings {
+                year,
                 day: day!(4),
                 part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
+                part_2: None,
+                total_nanos: 9e+7,
+                reports: vec![],
             },
         ]
     }
@@ -134,50 +293,78 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
+        assert_eq!(s.contains("### 2023 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2023 --->").count(), 2);
+        assert_eq!(s.matches("### 2023 Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn keeps_tables_of_other_years() {
+        let mut s = format!("foo\n{}\nbaz", MARKER);
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2021))).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2022))[..1].to_vec()).unwrap();
+
+        let positions: Vec<usize> = ["2021", "2022", "2023"]
+            .iter()
+            .map(|y| s.find(&format!("### {y} Benchmarks")).unwrap())
+            .collect();
+        assert!(positions.is_sorted());
+        assert!(s.contains("| [2022](#2022-benchmarks) | 1 | `30.00ms` |"));
+        assert!(s.contains("**Total: 410.00ms**"));
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(year!(2023))).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "| Year | Days | Total |",
+            "| :---: | :---: | :---: |",
+            "| [2023](#2023-benchmarks) | 3 | `190.00ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "",
+            "<!--- benchmarking table 2023 --->",
+            "### 2023 Benchmarks",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023_04.rs) | `40ms` | `-` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2023 --->",
             "baz",
         ]
         .join("\n");
@@ -0,0 +1,105 @@
+/// Module that lets one executable list and run solutions in-process.
+///
+/// With the opt-in `registry` feature, a build script compiles every `src/bin/{year}_{day}.rs` into
+/// the library and collects the [`Solution`] each of them registers through the `solution!`
+/// macro. Commands then run registered days directly instead of spawning `cargo run` per day.
+/// Without the feature, the registry is empty.
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::template::options::RunOptions;
+use crate::template::report::{self, Outcome, PartReport};
+use crate::template::runner;
+use crate::{Day, Year};
+
+/// Whether a solution was abandoned after a timeout and may still be running.
+static ABANDONED: AtomicBool = AtomicBool::new(false);
+
+/// A solution as registered by the `solution!` macro.
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Runs, prints and reports the parts selected by the options for an input.
+    pub solve: fn(&str, &RunOptions),
+}
+
+#[cfg(feature = "registry")]
+mod generated {
+    use super::Solution;
+
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+/// Returns every registered solution, sorted by year and day.
+#[must_use]
+pub fn solutions() -> &'static [&'static Solution] {
+    #[cfg(feature = "registry")]
+    return generated::SOLUTIONS;
+
+    #[cfg(not(feature = "registry"))]
+    return &[];
+}
+
+/// Returns the registered solution of a day.
+#[must_use]
+pub fn find(year: Year, day: Day) -> Option<&'static Solution> {
+    solutions()
+        .iter()
+        .find(|s| s.year == year && s.day == day)
+        .copied()
+}
+
+/// Runs a solution in-process against the input selected by `options` and returns the reports of
+/// its parts.
+///
+/// Panics of the solution (e.g. a missing input file) are caught and printed, and yield the reports
+/// of the parts that finished before. The solution runs on its own thread, which is abandoned when
+/// a part times out: it keeps running in the background until the process exits.
+pub fn run(solution: &Solution, options: &RunOptions, is_quiet: bool) -> Vec<PartReport> {
+    let (year, day, solve) = (solution.year, solution.day, solution.solve);
+
+    runner::install_panic_hook();
+    runner::set_quiet(is_quiet);
+    let (finished, stopped) = mpsc::channel();
+    runner::set_timeout_sender(Some(finished.clone()));
+
+    let mut reports = report::capture(|| {
+        let options = options.clone();
+        thread::spawn(move || {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+                for options in options.split_by_input(year, day) {
+                    let input = options.read_input(year, day);
+                    solve(&input, &options);
+                }
+            }));
+            let _ = finished.send(());
+        });
+
+        // returns once the solution finished or one of its parts timed out.
+        let _ = stopped.recv();
+    });
+
+    runner::set_timeout_sender(None);
+    runner::set_quiet(false);
+
+    // an abandoned solution may still report into the runs of later days.
+    reports.retain(|r| r.year == year && r.day == day);
+
+    if reports
+        .iter()
+        .any(|r| matches!(r.outcome, Outcome::TimedOut { .. }))
+    {
+        ABANDONED.store(true, Ordering::Relaxed);
+    }
+
+    reports
+}
+
+/// Returns `true` if a solution timed out in this process. Its thread keeps using the CPU until
+/// the process exits, which slows down the solutions that run after it.
+#[must_use]
+pub fn has_abandoned() -> bool {
+    ABANDONED.load(Ordering::Relaxed)
+}
@@ -0,0 +1,226 @@
+/// Machine-readable protocol between solution binaries and the runners that invoke them.
+///
+/// When the [`REPORT_FILE_ENV`] environment variable is set, every solved part appends one JSON
+/// record (one per line) to the file it points to. This lets `all` collect answers and timings
+/// without scraping the human-readable output. Solutions that run in-process hand their reports
+/// over through [`capture`] instead.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::{Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Day, Part, Year};
+
+/// Name of the environment variable that holds the path of the report file.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// The outcome of running a single part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub year: Year,
+    pub day: Day,
+    /// The part this report belongs to, or [`None`] for a shared parse step.
+    pub part: Option<Part>,
+    pub answer: Option<String>,
+    pub mean_nanos: u64,
+    pub median_nanos: u64,
+    pub min_nanos: u64,
+    pub max_nanos: u64,
+    pub p95_nanos: u64,
+    pub std_dev_nanos: u64,
+    pub samples: u64,
+    pub outliers: u64,
+    #[serde(default, skip_serializing_if = "Outcome::is_completed")]
+    pub outcome: Outcome,
+}
+
+#[cfg(feature = "test_lib")]
+impl PartReport {
+    /// A completed report of 100 samples around `median_nanos`, with a distinct value per field.
+    #[must_use]
+    pub fn mock(
+        year: Year,
+        day: Day,
+        part: Option<Part>,
+        answer: Option<&str>,
+        median_nanos: u64,
+    ) -> Self {
+        PartReport {
+            year,
+            day,
+            part,
+            answer: answer.map(Into::into),
+            mean_nanos: median_nanos + 1,
+            median_nanos,
+            min_nanos: median_nanos.saturating_sub(1),
+            max_nanos: median_nanos + 5,
+            p95_nanos: median_nanos + 4,
+            std_dev_nanos: median_nanos / 100,
+            samples: 100,
+            outliers: 3,
+            outcome: Outcome::Completed,
+        }
+    }
+}
+
+/// Whether a part ran to completion. Panicked and timed out parts report no answer.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    #[default]
+    Completed,
+    Panicked {
+        message: String,
+    },
+    TimedOut {
+        secs: u64,
+    },
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        *self == Outcome::Completed
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Completed => f.write_str("completed"),
+            Outcome::Panicked { message } => write!(f, "panicked: {message}"),
+            Outcome::TimedOut { secs } => write!(f, "timed out after {secs}s"),
+        }
+    }
+}
+
+/// Reports emitted while [`capture`] runs.
+static CAPTURED: Mutex<Option<Vec<PartReport>>> = Mutex::new(None);
+
+/// Runs `f` and returns the reports it emitted, instead of writing them to the report file.
+pub fn capture(f: impl FnOnce()) -> Vec<PartReport> {
+    *CAPTURED.lock().unwrap_or_else(PoisonError::into_inner) = Some(vec![]);
+    f();
+    CAPTURED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+        .unwrap_or_default()
+}
+
+/// Appends `report` to the report file if [`REPORT_FILE_ENV`] is set.
+pub fn emit(report: &PartReport) -> io::Result<()> {
+    if let Some(captured) = CAPTURED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+    {
+        captured.push(report.clone());
+        return Ok(());
+    }
+
+    let Some(path) = env::var_os(REPORT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let mut line = serde_json::to_string(report)?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Prints reports to stdout, one JSON record per line, for `--format json`.
+pub fn print_json(reports: &[PartReport]) {
+    for report in reports {
+        match serde_json::to_string(report) {
+            Ok(line) => println!("{line}"),
+            Err(e) => eprintln!("Failed to serialize report: {e}"),
+        }
+    }
+}
+
+/// Reads every report from a report file. A missing file yields no reports.
+pub fn read(path: &Path) -> io::Result<Vec<PartReport>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    parse(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn parse(contents: &str) -> serde_json::Result<Vec<PartReport>> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture, emit, parse, Outcome, PartReport};
+    use crate::{day, year, Part};
+
+    fn get_mock_report() -> PartReport {
+        PartReport::mock(
+            year!(2023),
+            day!(9),
+            Some(Part::One),
+            Some("(1, 2) @ 3 samples)"),
+            73,
+        )
+    }
+
+    #[test]
+    fn round_trips_reports() {
+        let report = get_mock_report();
+        let line = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            parse(&format!("{line}\n{line}\n")).unwrap(),
+            vec![report.clone(), report]
+        );
+    }
+
+    #[test]
+    fn serializes_year_and_day_as_numbers() {
+        let line = serde_json::to_string(&get_mock_report()).unwrap();
+        assert!(line.starts_with(r#"{"year":2023,"day":9,"part":1,"#));
+    }
+
+    #[test]
+    fn serializes_failed_outcomes() {
+        let mut report = get_mock_report();
+        assert!(!serde_json::to_string(&report).unwrap().contains("outcome"));
+
+        report.outcome = Outcome::TimedOut { secs: 10 };
+        let line = serde_json::to_string(&report).unwrap();
+        assert!(line.ends_with(r#","outcome":{"timed_out":{"secs":10}}}"#));
+        assert_eq!(parse(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        let line = r#"{"year":2023,"day":26,"part":1,"answer":null,"mean_nanos":1,"median_nanos":1,"min_nanos":1,"max_nanos":1,"p95_nanos":1,"std_dev_nanos":0,"samples":1,"outliers":0}"#;
+        assert!(parse(line).is_err());
+    }
+
+    #[test]
+    fn captures_reports_in_process() {
+        let report = get_mock_report();
+        let captured = capture(|| emit(&report).unwrap());
+        assert_eq!(captured, vec![report]);
+        assert!(capture(|| {}).is_empty());
+    }
+}
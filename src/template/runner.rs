@@ -1,73 +1,449 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::{Day, Year};
+use crate::template::answers::{self, Check};
+use crate::template::benchmark_history::{self, HistoryEntry};
+use crate::template::config;
+use crate::template::ocr;
+use crate::template::options::{Format, RunOptions};
+use crate::template::registry::{self, Solution};
+use crate::template::report::{self, Outcome, PartReport};
+use crate::template::submissions;
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Part, Year};
+use std::cell::Cell;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Mutex, Once, PoisonError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process, thread};
 
 use super::ANSI_BOLD;
 
+/// Whether the output of solutions is suppressed, e.g. when `verify` runs them in-process.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Notified when a part times out, so that [`registry::run`] stops waiting for the solution.
+static TIMEOUT_SENDER: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+thread_local! {
+    /// Whether the current thread runs a part inside [`catch_panic`].
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Like [`print!`], unless output is suppressed by [`set_quiet`].
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// Like [`println!`], unless output is suppressed by [`set_quiet`].
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Suppresses or restores the output of solutions run in this process.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Sets where timeouts are announced while a solution runs, see [`registry::run`].
+pub(crate) fn set_timeout_sender(sender: Option<Sender<()>>) {
+    *TIMEOUT_SENDER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = sender;
+}
+
+/// Runs a solution binary: parses its options, reads the input and runs the solution.
+/// Exits with a non-zero status if the solution panicked or timed out.
+pub fn run_binary(solution: &Solution) {
+    let options = RunOptions::from_env();
+    let reports = registry::run(solution, &options, options.format == Format::Json);
+
+    match options.format {
+        Format::Text => {
+            for report in &reports {
+                if let Err(e) = report::emit(report) {
+                    eprintln!("Failed to write report: {e}");
+                }
+            }
+        }
+        Format::Json => report::print_json(&reports),
+    }
+
+    // returning from `main` also stops a part that timed out.
+    if reports.is_empty() || reports.iter().any(|r| !r.outcome.is_completed()) {
+        process::exit(1);
+    }
+}
+
 pub fn run_part<I: Clone, T: Display>(
     func: impl Fn(I) -> Option<T>,
     input: I,
     year: Year,
     day: Day,
-    part: u8,
+    part: Part,
+    options: &RunOptions,
 ) {
+    if !options.runs(part) {
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let watched = (year, day, Some(part));
+    let Ok((result, timing)) = run_timed(func, input, options, watched, |result| {
+        print_result(result, &part_str, "");
+    }) else {
+        return;
+    };
+
+    let answer = result.as_ref().map(to_answer);
+
+    // known answers belong to the puzzle input.
+    let check = match &answer {
+        Some(answer) if options.is_puzzle_input() => check_answer(year, day, part, answer),
+        _ => Check::Unknown,
+    };
+
+    let mark = match check {
+        Check::Unknown => String::new(),
+        check => format!(" {check}"),
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &format!("{mark}{}", format_duration(&timing)),
+    );
+
+    record(
+        timing.to_report(year, day, Some(part), answer.clone()),
+        options,
+    );
+
+    if let Some(answer) = answer {
+        submit_result(answer, year, day, part, options);
+    }
+}
+
+/// Runs the parse step of a solution and reports its timing.
+/// The parsed input is shared by both parts, so their timings exclude parsing.
+pub fn run_parse<I: Clone, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+) -> P {
+    let watched = (year, day, None);
+    let Ok((parsed, timing)) = run_timed(func, input, options, watched, |_| out!("Parse: ✔"))
+    else {
+        // both parts need the parsed input, so the solution cannot go on.
+        panic::resume_unwind(Box::new("parsing the input panicked"));
+    };
+
+    out!("\r");
+    outln!("Parse: ✔{}", format_duration(&timing));
+
+    record(timing.to_report(year, day, None, None), options);
+
+    parsed
+}
+
+/// Prints and reports a parse step or part that did not complete.
+fn report_failure(year: Year, day: Day, part: Option<Part>, outcome: Outcome) {
+    let step = part.map_or_else(|| "Parse".to_string(), |part| format!("Part {part}"));
+    match &outcome {
+        Outcome::Completed => return,
+        Outcome::Panicked { message } => {
+            out!("\r");
+            outln!("{step}: 💥 {message}");
+        }
+        Outcome::TimedOut { secs } => {
+            out!("\r");
+            outln!("{step}: ⏱ timed out after {secs}s");
+        }
+    }
+
+    let mut report = Timing::default().to_report(year, day, part, None);
+    report.outcome = outcome;
+    if let Err(e) = report::emit(&report) {
+        eprintln!("Failed to write report: {e}");
+    }
+}
+
+/// Reports a timeout unless the returned sender is dropped within `timeout`.
+fn watch(year: Year, day: Day, part: Option<Part>, timeout: Duration) -> Sender<()> {
+    let (done, finished) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        if finished.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+            let secs = timeout.as_secs();
+            report_failure(year, day, part, Outcome::TimedOut { secs });
+
+            if let Some(sender) = TIMEOUT_SENDER
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .as_ref()
+            {
+                let _ = sender.send(());
+            }
+        }
+    });
+
+    done
+}
+
+/// Installs a panic hook that stays silent for panics caught by [`catch_panic`] and passes every
+/// other panic on to the previous hook. The hook is process-wide and installed only once, as
+/// threads of abandoned solutions may still panic while later solutions run.
+pub(crate) fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANIC.get() {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs `f`, turning a panic into its message. Its panic output is suppressed by the hook of
+/// [`install_panic_hook`].
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    install_panic_hook();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let was_catching = CATCHING_PANIC.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANIC.set(was_catching);
+
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
 
-    if let Some(result) = result {
-        submit_result(result, year, day, part);
+/// Returns the answer of a result: the letters it draws if it is ASCII art, or else the result.
+fn to_answer<T: Display>(result: &T) -> String {
+    let result = result.to_string();
+    ocr::parse(&result).unwrap_or(result)
+}
+
+/// Compares an answer against the known answer of its part.
+fn check_answer(year: Year, day: Day, part: Part, answer: &str) -> Check {
+    match answers::load(year, day) {
+        Ok(answers) => answers.check(part, answer),
+        Err(e) => {
+            eprintln!("Failed to read known answers: {e}");
+            Check::Unknown
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Emits a report and, for benchmarked release builds on the puzzle input, appends it to the
+/// benchmark history.
+fn record(report: PartReport, options: &RunOptions) {
+    if let Err(e) = report::emit(&report) {
+        eprintln!("Failed to write report: {e}");
+    }
+
+    // debug builds are too slow for their timings to be meaningful.
+    if options.time && options.is_puzzle_input() && !cfg!(debug_assertions) {
+        if let Err(e) = benchmark_history::append(&HistoryEntry::new(report)) {
+            eprintln!("Failed to write benchmark history: {e}");
+        }
+    }
+}
+
+/// Timing statistics collected while running a solution part.
+///
+/// Samples outside of the [Tukey fences](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences)
+/// (1.5 times the interquartile range) are counted as outliers and excluded from every statistic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timing {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    pub samples: u128,
+    pub outliers: u128,
+}
+
+impl Timing {
+    /// Summarizes a non-empty list of measured durations.
+    fn from_durations(durations: &[Duration]) -> Self {
+        assert!(!durations.is_empty(), "no durations to summarize");
+
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+
+        let (q1, q3) = (percentile(&sorted, 0.25), percentile(&sorted, 0.75));
+        let fence = (q3 - q1) * 3 / 2;
+        let (low, high) = (q1.saturating_sub(fence), q3 + fence);
+
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .copied()
+            .filter(|d| (low..=high).contains(d))
+            .collect();
+
+        if kept.is_empty() {
+            return Self::default();
+        }
+
+        let mean = average_duration(&kept);
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = kept
+            .iter()
+            .map(|d| (d.as_nanos().abs_diff(mean) as f64).powi(2))
+            .sum::<f64>()
+            / kept.len() as f64;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            mean: Duration::from_nanos(mean as u64),
+            median: percentile(&kept, 0.5),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            p95: percentile(&kept, 0.95),
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            samples: kept.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn to_report(
+        self,
+        year: Year,
+        day: Day,
+        part: Option<Part>,
+        answer: Option<String>,
+    ) -> PartReport {
+        PartReport {
+            year,
+            day,
+            part,
+            answer,
+            mean_nanos: self.mean.as_nanos() as u64,
+            median_nanos: self.median.as_nanos() as u64,
+            min_nanos: self.min.as_nanos() as u64,
+            max_nanos: self.max.as_nanos() as u64,
+            p95_nanos: self.p95.as_nanos() as u64,
+            std_dev_nanos: self.std_dev.as_nanos() as u64,
+            samples: self.samples as u64,
+            outliers: self.outliers as u64,
+            outcome: Outcome::Completed,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty list of durations.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    assert!(!sorted.is_empty(), "percentile of an empty list");
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Run a solution part. The behavior differs depending on whether it is timed (`--time`):
+///  1. if not, the function is executed once.
+///  2. if so, the function is warmed up and then benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// The first execution is subject to the timeout of the options. If it panics, the panic is
+/// reported for the `watched` part and [`Err`] is returned. Benchmarking stops collecting samples
+/// once it has run for as long as the timeout.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
+    watched: (Year, Day, Option<Part>),
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> Result<(T, Timing), ()> {
+    let (year, day, part) = watched;
+    let watchdog = options
+        .timeout()
+        .map(|timeout| watch(year, day, part, timeout));
+
     let timer = Instant::now();
-    let result = func(input.clone());
+    let result = catch_panic(|| func(input.clone()));
     let base_time = timer.elapsed();
 
+    drop(watchdog);
+
+    let result = match result {
+        Ok(result) => result,
+        Err(message) => {
+            report_failure(year, day, part, Outcome::Panicked { message });
+            return Err(());
+        }
+    };
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let timing = if options.time {
+        bench(func, input, &base_time, options.timeout())
     } else {
-        (base_time, 1)
+        Timing::from_durations(&[base_time])
     };
 
-    (result, run.0, run.1)
+    Ok((result, timing))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    timeout: Option<Duration>,
+) -> Timing {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let settings = &config::get().benchmark;
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_samples, settings.max_samples);
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let is_over = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+    // warm up caches and the branch predictor before measuring.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        if is_over() {
+            break;
+        }
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        if is_over() {
+            break;
+        }
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -75,11 +451,11 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    if timers.is_empty() {
+        timers.push(*base_time);
+    }
+
+    Timing::from_durations(&timers)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -90,11 +466,21 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(timing: &Timing) -> String {
+    let Timing {
+        median,
+        std_dev,
+        samples,
+        outliers,
+        ..
+    } = timing;
+
+    match (samples, outliers) {
+        (1, 0) => format!(" ({median:.1?})"),
+        (_, 0) => format!(" ({median:.1?} ± {std_dev:.1?} @ {samples} samples)"),
+        (_, _) => format!(
+            " ({median:.1?} ± {std_dev:.1?} @ {samples} samples, {outliers} outliers rejected)"
+        ),
     }
 }
 
@@ -103,72 +489,114 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            let result = result.to_string();
+            if result.contains('\n') {
+                let str = match ocr::parse(&result) {
+                    Some(letters) => {
+                        format!("{part}: ▼ {ANSI_BOLD}{letters}{ANSI_RESET}{duration_str}")
+                    }
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    out!("\r");
+                    outln!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{part}: ✖             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    year: Year,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Try to submit one part of the solution through the configured backend
+/// (see [`crate::template::aoc::backend`]) if it is the part passed to `--submit`.
+/// Answers that the submission log shows to be wrong are not submitted.
+/// Exits if the submission fails: only `solve --submit` submits, so `all` and `verify` never exit here.
+fn submit_result<T: Display>(result: T, year: Year, day: Day, part: Part, options: &RunOptions) {
+    if options.submit != Some(part) {
+        return;
+    }
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    outln!("Submitting result...");
+    match submissions::submit(year, day, part, &result.to_string()) {
+        Ok(verdict) => outln!("Answer was {ANSI_BOLD}{verdict}{ANSI_RESET}."),
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
     }
+}
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{catch_panic, Timing};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    #[test]
+    fn single_sample() {
+        let timing = Timing::from_durations(&nanos(&[42]));
+        assert_eq!(timing.median, Duration::from_nanos(42));
+        assert_eq!(timing.p95, Duration::from_nanos(42));
+        assert_eq!(timing.std_dev, Duration::ZERO);
+        assert_eq!(timing.samples, 1);
+        assert_eq!(timing.outliers, 0);
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    #[test]
+    fn computes_statistics() {
+        let timing = Timing::from_durations(&nanos(&[10, 20, 30, 40, 50]));
+        assert_eq!(timing.mean, Duration::from_nanos(30));
+        assert_eq!(timing.median, Duration::from_nanos(30));
+        assert_eq!(timing.min, Duration::from_nanos(10));
+        assert_eq!(timing.max, Duration::from_nanos(50));
+        assert_eq!(timing.p95, Duration::from_nanos(50));
+        assert_eq!(timing.std_dev, Duration::from_nanos(14));
+        assert_eq!(timing.samples, 5);
+    }
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn rejects_outliers() {
+        let timing = Timing::from_durations(&nanos(&[10, 11, 10, 12, 11, 10, 5000]));
+        assert_eq!(timing.outliers, 1);
+        assert_eq!(timing.samples, 6);
+        assert_eq!(timing.max, Duration::from_nanos(12));
+        assert_eq!(timing.mean, Duration::from_nanos(10));
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    #[should_panic(expected = "no durations")]
+    fn rejects_empty_samples() {
+        let _ = Timing::from_durations(&[]);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(year, day, part, &result.to_string()))
+    #[test]
+    fn catches_panic_messages() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+        assert_eq!(catch_panic(|| panic!("boom")), Err::<(), _>("boom".into()));
+        assert_eq!(
+            catch_panic(|| panic!("bad input {}", 7)),
+            Err::<(), _>("bad input 7".into())
+        );
+    }
 }
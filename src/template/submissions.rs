@@ -0,0 +1,308 @@
+/// Module that keeps a log of submitted answers in `data/submissions/{year}_{day}.jsonl`.
+/// The log is used to refuse submissions that are known to be wrong before they reach the website.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{answers, aoc, aoc_client::Verdict, config};
+use crate::{Day, Part, Year};
+
+/// A single submitted answer and the verdict of the website.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub part: Part,
+    pub answer: String,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+/// The reason a submission was refused without contacting the website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong,
+    NotBelow { bound: String },
+    NotAbove { bound: String },
+    RateLimited { remaining_secs: u64 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with {answer}")
+            }
+            Refusal::KnownWrong => f.write_str("this answer was already submitted and is wrong"),
+            Refusal::NotBelow { bound } => write!(f, "{bound} was already too high"),
+            Refusal::NotAbove { bound } => write!(f, "{bound} was already too low"),
+            Refusal::RateLimited { remaining_secs } => {
+                write!(f, "rate-limited, {remaining_secs}s left to wait")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Aoc(aoc::AocError),
+    IO(io::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "refusing to submit: {refusal}"),
+            SubmitError::Aoc(e) => write!(f, "{e}"),
+            SubmitError::IO(e) => write!(f, "could not access submission log: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for SubmitError {
+    fn from(e: io::Error) -> Self {
+        SubmitError::IO(e)
+    }
+}
+
+#[must_use]
+pub fn get_path_for_submissions(year: Year, day: Day) -> PathBuf {
+    config::get()
+        .data_path("submissions")
+        .join(format!("{year}_{day}.jsonl"))
+}
+
+/// Loads every submission of a day, oldest first. A missing file yields no submissions.
+pub fn load(year: Year, day: Day) -> io::Result<Vec<Submission>> {
+    let contents = match fs::read_to_string(get_path_for_submissions(year, day)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(io::Error::from))
+        .collect()
+}
+
+fn append(year: Year, day: Day, submission: &Submission) -> io::Result<()> {
+    let path = get_path_for_submissions(year, day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_string(submission)?;
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Checks whether `answer` may be submitted for `part`, given the previous submissions of the day.
+pub fn check(
+    submissions: &[Submission],
+    part: Part,
+    answer: &str,
+    now: u64,
+) -> Result<(), Refusal> {
+    let answer = answer.trim();
+    let previous = submissions.iter().filter(|s| s.part == part);
+
+    if let Some(last) = submissions.last() {
+        if let Verdict::RateLimited {
+            wait_secs: Some(wait_secs),
+        } = last.verdict
+        {
+            let until = last.timestamp + wait_secs;
+            if now < until {
+                return Err(Refusal::RateLimited {
+                    remaining_secs: until - now,
+                });
+            }
+        }
+    }
+
+    let value = answer.parse::<i128>().ok();
+
+    for submission in previous {
+        let bound = submission.answer.parse::<i128>().ok();
+
+        match (&submission.verdict, value, bound) {
+            (Verdict::Correct, _, _) => {
+                return Err(Refusal::AlreadySolved {
+                    answer: submission.answer.clone(),
+                })
+            }
+            (Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow, _, _)
+                if submission.answer == answer =>
+            {
+                return Err(Refusal::KnownWrong)
+            }
+            (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                return Err(Refusal::NotBelow {
+                    bound: submission.answer.clone(),
+                })
+            }
+            (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                return Err(Refusal::NotAbove {
+                    bound: submission.answer.clone(),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Submits an answer unless the submission log shows that it is wrong, or the part already has a
+/// known answer. Every verdict is logged, and correct answers are recorded as known answers.
+pub fn submit(year: Year, day: Day, part: Part, answer: &str) -> Result<Verdict, SubmitError> {
+    let answer = answer.trim();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    if let Some(known) = answers::load(year, day)?.get(part) {
+        return Err(SubmitError::Refused(Refusal::AlreadySolved {
+            answer: known.into(),
+        }));
+    }
+
+    check(&load(year, day)?, part, answer, now).map_err(SubmitError::Refused)?;
+
+    let verdict = aoc::submit(year, day, part, answer).map_err(SubmitError::Aoc)?;
+
+    append(
+        year,
+        day,
+        &Submission {
+            timestamp: now,
+            part,
+            answer: answer.into(),
+            verdict: verdict.clone(),
+        },
+    )?;
+
+    if verdict == Verdict::Correct {
+        answers::store(year, day, part, answer)?;
+    }
+
+    Ok(verdict)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Refusal, Submission};
+    use crate::template::aoc_client::Verdict;
+    use crate::Part;
+
+    fn submission(timestamp: u64, part: Part, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp,
+            part,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn allows_first_submission() {
+        assert_eq!(check(&[], Part::One, "42", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let log = [submission(0, Part::One, "abc", Verdict::Incorrect)];
+        assert_eq!(check(&log, Part::One, "abc", 100), Err(Refusal::KnownWrong));
+        assert_eq!(
+            check(&log, Part::One, "abc\n", 100),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(check(&log, Part::One, "abd", 100), Ok(()));
+        assert_eq!(check(&log, Part::Two, "abc", 100), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = [
+            submission(0, Part::One, "100", Verdict::TooHigh),
+            submission(100, Part::One, "10", Verdict::TooLow),
+        ];
+        assert_eq!(
+            check(&log, Part::One, "150", 200),
+            Err(Refusal::NotBelow {
+                bound: "100".into()
+            })
+        );
+        assert_eq!(check(&log, Part::One, "10", 200), Err(Refusal::KnownWrong));
+        assert_eq!(
+            check(&log, Part::One, "5", 200),
+            Err(Refusal::NotAbove { bound: "10".into() })
+        );
+        assert_eq!(check(&log, Part::One, "50", 200), Ok(()));
+        assert_eq!(
+            check(&log, Part::One, " 150\n", 200),
+            Err(Refusal::NotBelow {
+                bound: "100".into()
+            })
+        );
+    }
+
+    #[test]
+    fn refuses_while_rate_limited() {
+        let log = [submission(
+            1000,
+            Part::One,
+            "1",
+            Verdict::RateLimited {
+                wait_secs: Some(60),
+            },
+        )];
+        assert_eq!(
+            check(&log, Part::Two, "2", 1030),
+            Err(Refusal::RateLimited { remaining_secs: 30 })
+        );
+        assert_eq!(check(&log, Part::Two, "2", 1060), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let log = [submission(0, Part::One, "42", Verdict::Correct)];
+        assert_eq!(
+            check(&log, Part::One, "43", 100),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_verdicts_inline() {
+        let line = serde_json::to_string(&submission(
+            5,
+            Part::One,
+            "7",
+            Verdict::RateLimited { wait_secs: Some(3) },
+        ))
+        .unwrap();
+        assert_eq!(
+            line,
+            r#"{"timestamp":5,"part":1,"answer":"7","verdict":"rate_limited","wait_secs":3}"#
+        );
+    }
+}
@@ -1,16 +1,77 @@
 use std::{error::Error, fmt::Display, str::FromStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use serde::{Deserialize, Serialize};
+
+use crate::calendar;
+use crate::day::AllDays;
+
+/// The year of the first event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// A year with an Advent of Code event, from 2015 up to the latest event that has started.
+///
+/// Events start on December 1st at midnight in UTC-5, so which years are valid depends on the
+/// current date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
 pub struct Year(u16);
 
 impl Year {
+    /// Creates a [`Year`] from the provided value if its event has started,
+    /// returns [`None`] otherwise.
     pub fn new(year: u16) -> Option<Self> {
-        if !(2015..=2023).contains(&year) {
+        Self::new_at(year, calendar::now_secs())
+    }
+
+    /// Creates a [`Year`] if its event has started at `unix_secs`.
+    pub fn new_at(year: u16, unix_secs: i64) -> Option<Self> {
+        if year < FIRST_YEAR || i64::from(year) > calendar::latest_event_year(unix_secs) {
             return None;
         }
         Some(Self(year))
     }
 
+    /// Returns the year of the latest event that has started.
+    #[must_use]
+    pub fn latest() -> Self {
+        Self::latest_at(calendar::now_secs())
+    }
+
+    /// Returns the year of the latest event that has started at `unix_secs`.
+    #[must_use]
+    pub fn latest_at(unix_secs: i64) -> Self {
+        let year = calendar::latest_event_year(unix_secs);
+        Self(u16::try_from(year).unwrap_or(FIRST_YEAR).max(FIRST_YEAR))
+    }
+
+    /// Returns the year of the next event to start.
+    #[must_use]
+    pub fn next() -> Self {
+        Self::next_at(calendar::now_secs())
+    }
+
+    /// Returns the year of the next event to start after `unix_secs`.
+    #[must_use]
+    pub fn next_at(unix_secs: i64) -> Self {
+        Self(Self::latest_at(unix_secs).0 + 1)
+    }
+
+    /// Returns the number of puzzles of the event: 25 until 2024, 12 from 2025 on.
+    #[must_use]
+    pub fn day_count(self) -> u8 {
+        if self.0 >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Returns an iterator over every day of the event.
+    #[must_use]
+    pub fn days(self) -> AllDays {
+        AllDays::until(self.day_count())
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(year: u16) -> Self {
@@ -41,6 +102,26 @@ impl PartialOrd<u16> for Year {
     }
 }
 
+/// Accepts any year from [`FIRST_YEAR`] on, whatever the current date, so that stored data
+/// deserializes the same way on every day. Use [`Year::new`] or parse a [`Year`] to also check
+/// that its event has started.
+impl TryFrom<u16> for Year {
+    type Error = YearFromStrError;
+
+    fn try_from(year: u16) -> Result<Self, Self::Error> {
+        if year < FIRST_YEAR {
+            return Err(YearFromStrError);
+        }
+        Ok(Self(year))
+    }
+}
+
+impl From<Year> for u16 {
+    fn from(year: Year) -> Self {
+        year.0
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl FromStr for Year {
@@ -60,26 +141,39 @@ impl Error for YearFromStrError {}
 
 impl Display for YearFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a year number between 2015 and 2024")
+        write!(
+            f,
+            "expecting a year number between {FIRST_YEAR} and {}",
+            Year::latest()
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every aoc year.
+/// An iterator that yields every aoc year up to the latest event that has started.
 pub fn all_years() -> AllYears {
     AllYears::new()
 }
 
-/// An iterator that yields every aoc year.
+/// An iterator that yields every aoc year up to the latest event that has started.
 pub struct AllYears {
     current: u16,
+    last: u16,
 }
 
 impl AllYears {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(Year::latest())
+    }
+
+    /// Creates an iterator over the years from [`FIRST_YEAR`] up to `last`.
+    pub fn until(last: Year) -> Self {
+        Self {
+            current: FIRST_YEAR,
+            last: last.0,
+        }
     }
 }
 
@@ -87,10 +181,10 @@ impl Iterator for AllYears {
     type Item = Year;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 2023 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at the first year and stops at a valid year.
         let year = Year(self.current);
         self.current += 1;
 
@@ -101,15 +195,18 @@ impl Iterator for AllYears {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Year`] value in a const context.
+///
+/// Only the lower bound can be checked at compile time, as the latest valid year depends on the
+/// current date.
 #[macro_export]
 macro_rules! year {
     ($year:expr) => {{
         const _ASSERT: () = assert!(
-            $year >= 2015 && $year <= 2023,
+            $year >= $crate::FIRST_YEAR,
             concat!(
                 "invalid year number `",
                 $year,
-                "`, expecting a value between 2015 and 2024"
+                "`, expecting a value from 2015 on"
             ),
         );
         $crate::Year::__new_unchecked($year)
@@ -120,11 +217,12 @@ macro_rules! year {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_years, Year};
+    use super::{all_years, AllYears, Year};
+    use crate::calendar::event_midnight;
 
     #[test]
     fn all_years_iterator() {
-        let mut iter = all_years();
+        let mut iter = AllYears::until(Year(2023));
 
         assert_eq!(iter.next(), Some(Year(2015)));
         assert_eq!(iter.next(), Some(Year(2016)));
@@ -136,6 +234,37 @@ mod tests {
         assert_eq!(iter.next(), Some(Year(2022)));
         assert_eq!(iter.next(), Some(Year(2023)));
         assert_eq!(iter.next(), None);
+
+        assert_eq!(all_years().next(), Some(Year(2015)));
+        assert_eq!(all_years().last(), Some(Year::latest()));
+    }
+
+    #[test]
+    fn validates_years_by_date() {
+        let before_2024 = event_midnight(2024, 12, 1) - 1;
+        assert_eq!(Year::new_at(2023, before_2024), Some(Year(2023)));
+        assert_eq!(Year::next_at(before_2024), Year(2024));
+        assert_eq!(Year::next_at(before_2024 + 1), Year(2025));
+        assert_eq!(Year::new_at(2024, before_2024), None);
+        assert_eq!(Year::new_at(2024, before_2024 + 1), Some(Year(2024)));
+        assert_eq!(Year::new_at(2014, before_2024), None);
+        assert_eq!(Year::latest_at(before_2024), Year(2023));
+    }
+
+    #[test]
+    fn deserializes_years_regardless_of_date() {
+        assert_eq!(Year::try_from(2100).unwrap(), Year(2100));
+        assert!(Year::try_from(2014).is_err());
+        assert_eq!(serde_json::from_str::<Year>("2100").unwrap(), Year(2100));
+        assert!(serde_json::from_str::<Year>("2014").is_err());
+        assert!("2100".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn counts_days_per_year() {
+        assert_eq!(Year(2024).day_count(), 25);
+        assert_eq!(Year(2025).day_count(), 12);
+        assert_eq!(Year(2025).days().last().map(u8::from), Some(12));
     }
 }
 